@@ -0,0 +1,184 @@
+use core::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::{AsyncBufRead, AsyncRead};
+use crate::{IoSliceMut, Result, DEFAULT_BUF_SIZE};
+
+/// The `AsyncBufReader<R>` struct adds buffering to any [`AsyncRead`]er.
+///
+/// This is the asynchronous version of [`BufReader`]. The buffer is an array
+/// of `N` bytes (1 KB by default) stored inline, so no allocation is needed.
+///
+/// [`BufReader`]: crate::BufReader
+pub struct AsyncBufReader<R, const N: usize = DEFAULT_BUF_SIZE> {
+    inner: R,
+    pos: usize,
+    filled: usize,
+    buf: [u8; N],
+}
+
+impl<R: AsyncRead> AsyncBufReader<R> {
+    /// Creates a new `AsyncBufReader<R>` with a default buffer capacity (1 KB).
+    pub const fn new(inner: R) -> AsyncBufReader<R> {
+        AsyncBufReader::new_inline(inner)
+    }
+}
+
+impl<R: AsyncRead, const N: usize> AsyncBufReader<R, N> {
+    /// Creates a new `AsyncBufReader<R, N>` with an inline buffer of `N`
+    /// bytes.
+    ///
+    /// `N` must not be zero; this is checked at compile time.
+    pub const fn new_inline(inner: R) -> AsyncBufReader<R, N> {
+        const { assert!(N > 0, "buffer of an AsyncBufReader must not be empty") };
+        AsyncBufReader {
+            inner,
+            pos: 0,
+            filled: 0,
+            buf: [0; N],
+        }
+    }
+}
+
+impl<R, const N: usize> AsyncBufReader<R, N> {
+    /// Gets a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying reader.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.project().inner
+    }
+
+    /// Returns a reference to the internally buffered data.
+    ///
+    /// Unlike [`poll_fill_buf`], this will not attempt to fill the buffer if
+    /// it is empty.
+    ///
+    /// [`poll_fill_buf`]: AsyncBufRead::poll_fill_buf
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.filled]
+    }
+
+    /// Returns the number of bytes the internal buffer can hold at once.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Unwraps this `AsyncBufReader<R>`, returning the underlying reader.
+    ///
+    /// Note that any leftover data in the internal buffer is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Splits a pinned reference into the pinned inner reader and the
+    /// unpinned buffer state.
+    fn project(self: Pin<&mut Self>) -> Projection<'_, R, N> {
+        // SAFETY: `inner` is structurally pinned: it is never moved out of a
+        // pinned `AsyncBufReader`. The other fields do not require pinning.
+        unsafe {
+            let this = self.get_unchecked_mut();
+            Projection {
+                inner: Pin::new_unchecked(&mut this.inner),
+                pos: &mut this.pos,
+                filled: &mut this.filled,
+                buf: &mut this.buf,
+            }
+        }
+    }
+}
+
+/// Field-wise borrow of a pinned [`AsyncBufReader`].
+struct Projection<'a, R, const N: usize> {
+    inner: Pin<&'a mut R>,
+    pos: &'a mut usize,
+    filled: &'a mut usize,
+    buf: &'a mut [u8; N],
+}
+
+impl<R: AsyncRead, const N: usize> AsyncRead for AsyncBufReader<R, N> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        // If we don't have any buffered data and we're doing a massive read
+        // (larger than our internal buffer), bypass our internal buffer
+        // entirely.
+        if self.pos == self.filled && buf.len() >= N {
+            let this = self.project();
+            *this.pos = 0;
+            *this.filled = 0;
+            return this.inner.poll_read(cx, buf);
+        }
+        let nread = match self.as_mut().poll_fill_buf(cx) {
+            Poll::Ready(Ok(mut rem)) => crate::Read::read(&mut rem, buf)?,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        };
+        self.consume(nread);
+        Poll::Ready(Ok(nread))
+    }
+
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize>> {
+        let total_len = bufs.iter().map(|b| b.len()).sum::<usize>();
+        if self.pos == self.filled && total_len >= N {
+            let this = self.project();
+            *this.pos = 0;
+            *this.filled = 0;
+            return this.inner.poll_read_vectored(cx, bufs);
+        }
+        let nread = match self.as_mut().poll_fill_buf(cx) {
+            Poll::Ready(Ok(mut rem)) => crate::Read::read_vectored(&mut rem, bufs)?,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        };
+        self.consume(nread);
+        Poll::Ready(Ok(nread))
+    }
+}
+
+impl<R: AsyncRead, const N: usize> AsyncBufRead for AsyncBufReader<R, N> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<&[u8]>> {
+        let this = self.project();
+        if *this.pos >= *this.filled {
+            match this.inner.poll_read(cx, this.buf) {
+                Poll::Ready(Ok(n)) => {
+                    *this.pos = 0;
+                    *this.filled = n;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(&this.buf[*this.pos..*this.filled]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.project();
+        *this.pos = core::cmp::min(*this.pos + amt, *this.filled);
+    }
+}
+
+impl<R: fmt::Debug, const N: usize> fmt::Debug for AsyncBufReader<R, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncBufReader")
+            .field("reader", &self.inner)
+            .field("buffer", &format_args!("{}/{}", self.filled - self.pos, N))
+            .finish()
+    }
+}
@@ -0,0 +1,309 @@
+#[cfg(feature = "alloc")]
+use core::mem::MaybeUninit;
+use core::{
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use axerrno::ax_err;
+
+use super::{AsyncRead, AsyncSeek, AsyncWrite};
+use crate::{Result, SeekFrom};
+
+/// An extension trait which adds utility methods to [`AsyncRead`] types.
+pub trait AsyncReadExt: AsyncRead {
+    /// Reads some bytes from this source into `buf`, returning how many bytes
+    /// were read.
+    ///
+    /// This is the asynchronous version of [`Read::read`].
+    ///
+    /// [`Read::read`]: crate::Read::read
+    fn read<'a>(&'a mut self, buf: &'a mut [u8]) -> ReadFuture<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadFuture { reader: self, buf }
+    }
+
+    /// Reads the exact number of bytes required to fill `buf`.
+    ///
+    /// This is the asynchronous version of [`Read::read_exact`]. If EOF is
+    /// reached first, an [`Io`] error is returned and the contents of `buf`
+    /// are unspecified.
+    ///
+    /// [`Read::read_exact`]: crate::Read::read_exact
+    /// [`Io`]: crate::Error::Io
+    fn read_exact<'a>(&'a mut self, buf: &'a mut [u8]) -> ReadExactFuture<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadExactFuture { reader: self, buf }
+    }
+
+    /// Reads all bytes until EOF from this source, appending them to `buf`.
+    ///
+    /// This is the asynchronous version of [`Read::read_to_end`]. On success,
+    /// the number of bytes read is returned.
+    ///
+    /// [`Read::read_to_end`]: crate::Read::read_to_end
+    #[cfg(feature = "alloc")]
+    fn read_to_end<'a>(&'a mut self, buf: &'a mut Vec<u8>) -> ReadToEndFuture<'a, Self>
+    where
+        Self: Unpin,
+    {
+        let start_len = buf.len();
+        ReadToEndFuture {
+            reader: self,
+            buf,
+            start_len,
+            initialized: 0,
+        }
+    }
+}
+
+impl<R: AsyncRead + ?Sized> AsyncReadExt for R {}
+
+/// An extension trait which adds utility methods to [`AsyncWrite`] types.
+pub trait AsyncWriteExt: AsyncWrite {
+    /// Writes some bytes from `buf` into this object, returning how many bytes
+    /// were written.
+    ///
+    /// This is the asynchronous version of [`Write::write`].
+    ///
+    /// [`Write::write`]: crate::Write::write
+    fn write<'a>(&'a mut self, buf: &'a [u8]) -> WriteFuture<'a, Self>
+    where
+        Self: Unpin,
+    {
+        WriteFuture { writer: self, buf }
+    }
+
+    /// Writes the entire buffer into this object.
+    ///
+    /// This is the asynchronous version of [`Write::write_all`].
+    ///
+    /// [`Write::write_all`]: crate::Write::write_all
+    fn write_all<'a>(&'a mut self, buf: &'a [u8]) -> WriteAllFuture<'a, Self>
+    where
+        Self: Unpin,
+    {
+        WriteAllFuture { writer: self, buf }
+    }
+
+    /// Flushes this object, ensuring that any buffered data reach their
+    /// destination.
+    ///
+    /// This is the asynchronous version of [`Write::flush`].
+    ///
+    /// [`Write::flush`]: crate::Write::flush
+    fn flush(&mut self) -> FlushFuture<'_, Self>
+    where
+        Self: Unpin,
+    {
+        FlushFuture { writer: self }
+    }
+}
+
+impl<W: AsyncWrite + ?Sized> AsyncWriteExt for W {}
+
+/// An extension trait which adds utility methods to [`AsyncSeek`] types.
+pub trait AsyncSeekExt: AsyncSeek {
+    /// Seeks to an offset, in bytes, in this stream.
+    ///
+    /// This is the asynchronous version of [`Seek::seek`].
+    ///
+    /// [`Seek::seek`]: crate::Seek::seek
+    fn seek(&mut self, pos: SeekFrom) -> SeekFuture<'_, Self>
+    where
+        Self: Unpin,
+    {
+        SeekFuture { seeker: self, pos }
+    }
+}
+
+impl<S: AsyncSeek + ?Sized> AsyncSeekExt for S {}
+
+/// Future for the [`read`](AsyncReadExt::read) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadFuture<'a, R: ?Sized> {
+    reader: &'a mut R,
+    buf: &'a mut [u8],
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> Future for ReadFuture<'_, R> {
+    type Output = Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        Pin::new(&mut *this.reader).poll_read(cx, this.buf)
+    }
+}
+
+/// Future for the [`read_exact`](AsyncReadExt::read_exact) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadExactFuture<'a, R: ?Sized> {
+    reader: &'a mut R,
+    buf: &'a mut [u8],
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> Future for ReadExactFuture<'_, R> {
+    type Output = Result;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        while !this.buf.is_empty() {
+            let n = match Pin::new(&mut *this.reader).poll_read(cx, this.buf) {
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+            if n == 0 {
+                return Poll::Ready(ax_err!(Io, "failed to read whole buffer"));
+            }
+            let buf = mem::take(&mut this.buf);
+            this.buf = &mut buf[n..];
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Future for the [`read_to_end`](AsyncReadExt::read_to_end) method.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadToEndFuture<'a, R: ?Sized> {
+    reader: &'a mut R,
+    buf: &'a mut Vec<u8>,
+    start_len: usize,
+    /// Number of bytes at the start of the spare capacity that are known to
+    /// be initialized, so that they are not zeroed again on the next read.
+    initialized: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<R: AsyncRead + Unpin + ?Sized> Future for ReadToEndFuture<'_, R> {
+    type Output = Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        const PROBE_SIZE: usize = 32;
+
+        let this = self.get_mut();
+        loop {
+            if this.buf.len() == this.buf.capacity() {
+                if let Err(e) = this.buf.try_reserve(PROBE_SIZE) {
+                    return Poll::Ready(ax_err!(NoMemory, e));
+                }
+            }
+
+            // Read into the spare capacity. Only the part that no earlier
+            // iteration has initialized is zeroed, so small reads don't make
+            // this quadratic.
+            let len = this.buf.len();
+            let spare = this.buf.spare_capacity_mut();
+            spare[this.initialized..].fill(MaybeUninit::new(0));
+            // SAFETY: the first `initialized` bytes were initialized by an
+            // earlier iteration, and the rest has just been zeroed.
+            let dst = unsafe { spare.assume_init_mut() };
+            let result = Pin::new(&mut *this.reader).poll_read(cx, dst);
+            let n = match result {
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(_)) | Poll::Pending => 0,
+            };
+            assert!(n <= dst.len(), "read more bytes than the buffer holds");
+            this.initialized = dst.len() - n;
+            // SAFETY: the first `n` bytes of the spare capacity were written
+            // by the reader.
+            unsafe { this.buf.set_len(len + n) };
+
+            match result {
+                Poll::Ready(Ok(0)) => return Poll::Ready(Ok(len - this.start_len)),
+                Poll::Ready(Ok(_)) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Future for the [`write`](AsyncWriteExt::write) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteFuture<'a, W: ?Sized> {
+    writer: &'a mut W,
+    buf: &'a [u8],
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> Future for WriteFuture<'_, W> {
+    type Output = Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        Pin::new(&mut *this.writer).poll_write(cx, this.buf)
+    }
+}
+
+/// Future for the [`write_all`](AsyncWriteExt::write_all) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteAllFuture<'a, W: ?Sized> {
+    writer: &'a mut W,
+    buf: &'a [u8],
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> Future for WriteAllFuture<'_, W> {
+    type Output = Result;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        while !this.buf.is_empty() {
+            let n = match Pin::new(&mut *this.writer).poll_write(cx, this.buf) {
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+            if n == 0 {
+                return Poll::Ready(ax_err!(Io, "failed to write whole buffer"));
+            }
+            this.buf = &this.buf[n..];
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Future for the [`flush`](AsyncWriteExt::flush) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct FlushFuture<'a, W: ?Sized> {
+    writer: &'a mut W,
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> Future for FlushFuture<'_, W> {
+    type Output = Result;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.get_mut().writer).poll_flush(cx)
+    }
+}
+
+/// Future for the [`seek`](AsyncSeekExt::seek) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SeekFuture<'a, S: ?Sized> {
+    seeker: &'a mut S,
+    pos: SeekFrom,
+}
+
+impl<S: AsyncSeek + Unpin + ?Sized> Future for SeekFuture<'_, S> {
+    type Output = Result<u64>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        Pin::new(&mut *this.seeker).poll_seek(cx, this.pos)
+    }
+}
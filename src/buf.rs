@@ -1,25 +1,75 @@
-use core::mem::MaybeUninit;
-
 use crate::{Read, Result, Write};
 
-const IO_BUF_SIZE: usize = 4096;
-
-pub trait Buf: Read {
+/// A source of bytes with a known length, read through contiguous chunks.
+///
+/// A `Buf` is typically a view of memory that is about to be written
+/// somewhere else, e.g. the user buffer of a `write(2)` syscall. It exposes
+/// its data as a sequence of contiguous chunks: [`chunk`] returns the current
+/// one and [`advance`] marks bytes as consumed. Nothing is ever copied by the
+/// `Buf` itself, so the caller decides where data goes and how much of it is
+/// accepted.
+///
+/// `Buf` does not require [`Read`]: [`chunk`] and [`advance`] are all a
+/// source needs, and [`read_into_buf`]/[`write_from_buf`] bridge to readers
+/// and writers.
+///
+/// [`chunk`]: Buf::chunk
+/// [`advance`]: Buf::advance
+pub trait Buf {
+    /// Returns the number of bytes between the current position and the end
+    /// of the buffer.
     fn remaining(&self) -> usize;
 
-    fn consume(&mut self, mut f: impl FnMut(&[u8]) -> Result<usize>) -> Result<usize> {
-        let size = self.remaining();
-        let mut buf = [const { MaybeUninit::uninit() }; IO_BUF_SIZE];
+    /// Returns a slice starting at the current position.
+    ///
+    /// The slice may be shorter than [`remaining`] (e.g. for non-contiguous
+    /// buffers), but it must not be empty unless [`remaining`] is `0`.
+    ///
+    /// [`remaining`]: Buf::remaining
+    fn chunk(&self) -> &[u8];
+
+    /// Advances the current position by `cnt` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Implementations may panic if `cnt > self.remaining()`.
+    fn advance(&mut self, cnt: usize);
+
+    /// Returns `true` if there are any more bytes to consume.
+    fn has_remaining(&self) -> bool {
+        self.remaining() > 0
+    }
+
+    /// Hands the remaining bytes to `f`, chunk by chunk, and advances past
+    /// the bytes it accepted.
+    ///
+    /// `f` returns the number of bytes it took from the given chunk. Only
+    /// those bytes are consumed, so no data is lost on short transfers.
+    /// Consumption stops when the buffer is exhausted, or after the first
+    /// chunk `f` does not take entirely.
+    ///
+    /// Returns the total number of bytes consumed. If `f` fails, the error
+    /// is returned only if no byte has been consumed yet; otherwise the count
+    /// so far is returned and the error is dropped, the same way `write(2)`
+    /// reports a partial transfer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` claims to have taken more bytes than it was given.
+    fn consume_with(&mut self, mut f: impl FnMut(&[u8]) -> Result<usize>) -> Result<usize> {
         let mut count = 0;
-        loop {
-            let len = buf.len().min(size - count);
-            if len == 0 {
-                break;
-            }
-            self.read(unsafe { buf[..len].assume_init_mut() })?;
-            let read = f(unsafe { buf[..len].assume_init_ref() })?;
-            count += read;
-            if read < len {
+        while self.has_remaining() {
+            let chunk = self.chunk();
+            let len = chunk.len();
+            let n = match f(chunk) {
+                Ok(n) => n,
+                Err(e) if count == 0 => return Err(e),
+                Err(_) => break,
+            };
+            assert!(n <= len, "consumed more bytes than provided");
+            self.advance(n);
+            count += n;
+            if n < len {
                 break;
             }
         }
@@ -27,25 +77,161 @@ pub trait Buf: Read {
     }
 }
 
-pub trait BufMut: Write {
+/// A sink of bytes with a known capacity, written through contiguous chunks.
+///
+/// A `BufMut` is typically a view of memory that is about to be filled from
+/// somewhere else, e.g. the user buffer of a `read(2)` syscall. It exposes
+/// its free space as a sequence of contiguous chunks: [`chunk_mut`] returns
+/// the current one and [`advance_mut`] marks bytes as filled.
+///
+/// Like [`Buf`], `BufMut` does not require [`Write`].
+///
+/// [`chunk_mut`]: BufMut::chunk_mut
+/// [`advance_mut`]: BufMut::advance_mut
+pub trait BufMut {
+    /// Returns the number of bytes that can be written from the current
+    /// position until the end of the buffer.
     fn remaining_mut(&self) -> usize;
 
-    fn fill(&mut self, mut f: impl FnMut(&mut [u8]) -> Result<usize>) -> Result<usize> {
-        let size = self.remaining_mut();
-        let mut buf = [const { MaybeUninit::uninit() }; IO_BUF_SIZE];
+    /// Returns a mutable slice starting at the current position.
+    ///
+    /// The slice may be shorter than [`remaining_mut`], but it must not be
+    /// empty unless [`remaining_mut`] is `0`. Its contents are unspecified;
+    /// callers should only write to it.
+    ///
+    /// [`remaining_mut`]: BufMut::remaining_mut
+    fn chunk_mut(&mut self) -> &mut [u8];
+
+    /// Advances the current position by `cnt` bytes, marking them as filled.
+    ///
+    /// # Panics
+    ///
+    /// Implementations may panic if `cnt > self.remaining_mut()`.
+    fn advance_mut(&mut self, cnt: usize);
+
+    /// Returns `true` if there is space for more bytes.
+    fn has_remaining_mut(&self) -> bool {
+        self.remaining_mut() > 0
+    }
+
+    /// Lets `f` write into the free space, chunk by chunk, and advances past
+    /// the bytes it filled.
+    ///
+    /// `f` returns the number of bytes it wrote at the start of the given
+    /// chunk. Only those bytes are marked as filled. Filling stops when the
+    /// buffer is full, or after the first chunk `f` does not fill entirely.
+    ///
+    /// Returns the total number of bytes filled. If `f` fails, the error is
+    /// returned only if no byte has been filled yet; otherwise the count so
+    /// far is returned and the error is dropped, the same way `read(2)`
+    /// reports a partial transfer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` claims to have written more bytes than it was given.
+    fn put_with(&mut self, mut f: impl FnMut(&mut [u8]) -> Result<usize>) -> Result<usize> {
         let mut count = 0;
-        loop {
-            let len = buf.len().min(size - count);
-            if len == 0 {
-                break;
+        while self.has_remaining_mut() {
+            let chunk = self.chunk_mut();
+            let len = chunk.len();
+            let (n, err) = match f(chunk) {
+                Ok(n) => (n, None),
+                Err(e) => (0, Some(e)),
+            };
+            assert!(n <= len, "filled more bytes than provided");
+            // Every `chunk_mut` is paired with an `advance_mut`, even if
+            // nothing was filled; `VecDeque<u8>` relies on this.
+            self.advance_mut(n);
+            match err {
+                Some(e) if count == 0 => return Err(e),
+                Some(_) => break,
+                None => {}
             }
-            let written = f(unsafe { buf[..len].assume_init_mut() })?;
-            count += written;
-            self.write(unsafe { buf[..written].assume_init_ref() })?;
-            if written < len {
+            count += n;
+            if n < len {
                 break;
             }
         }
         Ok(count)
     }
 }
+
+/// Moves up to `limit` bytes from `src` to `dst`, returning the number of
+/// bytes moved.
+///
+/// Each byte is copied exactly once, straight from a [`Buf::chunk`] into a
+/// [`BufMut::chunk_mut`]; no intermediate buffer is used. The transfer stops
+/// early when either side is exhausted. Moving between buffers cannot fail, so
+/// the returned count is always exact.
+pub fn transfer<S, D>(src: &mut S, dst: &mut D, limit: usize) -> usize
+where
+    S: Buf + ?Sized,
+    D: BufMut + ?Sized,
+{
+    let mut count = 0;
+    while count < limit && src.has_remaining() && dst.has_remaining_mut() {
+        let chunk = src.chunk();
+        let out = dst.chunk_mut();
+        let n = chunk.len().min(out.len()).min(limit - count);
+        out[..n].copy_from_slice(&chunk[..n]);
+        src.advance(n);
+        dst.advance_mut(n);
+        count += n;
+    }
+    count
+}
+
+/// Reads up to `limit` bytes from `reader` directly into `dst`, returning the
+/// number of bytes moved.
+///
+/// The reader fills the chunks of `dst` in place, so each byte is copied only
+/// once. The transfer stops at the first short read (including EOF).
+///
+/// Errors follow [`BufMut::put_with`]: an error is returned only if it occurs
+/// before any byte has been moved. After partial progress the error is
+/// discarded and the number of bytes moved so far is returned; a persistent
+/// error will surface again on the next call.
+pub fn read_into_buf<R, D>(reader: &mut R, dst: &mut D, limit: usize) -> Result<usize>
+where
+    R: Read + ?Sized,
+    D: BufMut + ?Sized,
+{
+    let mut left = limit;
+    dst.put_with(|chunk| {
+        let len = chunk.len().min(left);
+        if len == 0 {
+            return Ok(0);
+        }
+        let n = reader.read(&mut chunk[..len])?;
+        left -= n;
+        Ok(n)
+    })
+}
+
+/// Writes up to `limit` bytes from `src` directly to `writer`, returning the
+/// number of bytes moved.
+///
+/// The writer reads the chunks of `src` in place, so each byte is copied only
+/// once. Only the bytes accepted by the writer are consumed from `src`, and
+/// the transfer stops at the first short write.
+///
+/// Errors follow [`Buf::consume_with`]: an error is returned only if it occurs
+/// before any byte has been moved. After partial progress the error is
+/// discarded and the number of bytes moved so far is returned; a persistent
+/// error will surface again on the next call.
+pub fn write_from_buf<S, W>(src: &mut S, writer: &mut W, limit: usize) -> Result<usize>
+where
+    S: Buf + ?Sized,
+    W: Write + ?Sized,
+{
+    let mut left = limit;
+    src.consume_with(|chunk| {
+        let len = chunk.len().min(left);
+        if len == 0 {
+            return Ok(0);
+        }
+        let n = writer.write(&chunk[..len])?;
+        left -= n;
+        Ok(n)
+    })
+}
@@ -0,0 +1,200 @@
+use core::fmt;
+
+use super::{BufWriter, IntoInnerError};
+use crate::{Result, Write};
+
+/// Wraps a writer and buffers output to it, flushing whenever a newline
+/// (`0x0a`, `'\n'`) is detected.
+///
+/// The [`BufWriter`] struct wraps a writer and buffers its output. But it only
+/// does this batched write when it goes out of scope, or when the internal
+/// buffer is full. Sometimes, you'd prefer to write each line as it's
+/// completed, rather than the entire buffer at once. Enter `LineWriter`. It
+/// does exactly that, which makes it a good fit for consoles and TTYs.
+///
+/// Like [`BufWriter`], a `LineWriter`'s buffer will also be flushed when the
+/// `LineWriter` goes out of scope or when its internal buffer is full.
+pub struct LineWriter<W: Write> {
+    inner: BufWriter<W>,
+}
+
+impl<W: Write> LineWriter<W> {
+    /// Creates a new `LineWriter`.
+    pub fn new(inner: W) -> LineWriter<W> {
+        // Lines typically aren't that long, don't use a giant buffer
+        LineWriter::with_capacity(1024, inner)
+    }
+
+    /// Creates a new `LineWriter` with at least the specified capacity for
+    /// the internal buffer.
+    pub fn with_capacity(capacity: usize, inner: W) -> LineWriter<W> {
+        LineWriter {
+            inner: BufWriter::with_capacity(capacity, inner),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Caution must be taken when calling methods on the mutable reference
+    /// returned as extra writes could corrupt the output stream.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+
+    /// Returns a reference to the internally buffered data, i.e. the trailing
+    /// partial line that has not been written out yet.
+    pub fn buffer(&self) -> &[u8] {
+        self.inner.buffer()
+    }
+
+    /// Unwraps this `LineWriter`, returning the underlying writer.
+    ///
+    /// The internal buffer is written out before returning the writer.
+    ///
+    /// # Errors
+    ///
+    /// An [`Err`] will be returned if an error occurs while flushing the
+    /// buffer.
+    pub fn into_inner(self) -> core::result::Result<W, IntoInnerError<LineWriter<W>>> {
+        self.inner
+            .into_inner()
+            .map_err(|err| err.new_wrapped(|inner| LineWriter { inner }))
+    }
+
+    fn flush_if_completed_line(&mut self) -> Result<()> {
+        match self.inner.buffer().last().copied() {
+            Some(b'\n') => self.inner.flush_buf(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<W: Write> Write for LineWriter<W> {
+    /// Writes some data into this `LineWriter`, flushing through to the inner
+    /// writer up to and including the last newline in `buf`.
+    ///
+    /// If `buf` contains no newline, it is simply buffered (after flushing a
+    /// previously completed line, if any). The return value never counts
+    /// bytes that were neither written to the inner writer nor buffered, so a
+    /// short write or an error from the inner writer does not lose data.
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let newline_idx = match buf.iter().rposition(|&b| b == b'\n') {
+            // If there are no new newlines (that is, if this write is less
+            // than one line), just do a regular buffered write (which may
+            // flush if we exceed the inner buffer's size)
+            None => {
+                self.flush_if_completed_line()?;
+                return self.inner.write(buf);
+            }
+            // Otherwise, arrange for the lines to be written directly to the
+            // inner writer.
+            Some(newline_idx) => newline_idx + 1,
+        };
+
+        // Flush existing content to prepare for our write. We have to do this
+        // before attempting to write `buf` in order to make sure that
+        // if this write returns Err(_), we'll have a clean slate.
+        self.inner.flush_buf()?;
+
+        // This is what we're going to try to write directly to the inner
+        // writer. The rest will be buffered, if nothing goes wrong.
+        let lines = &buf[..newline_idx];
+
+        // Write `lines` directly to the inner writer. In keeping with the
+        // `write` convention, make at most one attempt to add new (unbuffered)
+        // data. Because this write doesn't touch the BufWriter state directly,
+        // and the buffer is known to be empty, we don't need to worry about
+        // self.inner.panicked here.
+        let flushed = self.inner.get_mut().write(lines)?;
+
+        // If buffer returns Ok(0), propagate that to the caller without
+        // doing additional buffering; otherwise we're just guaranteeing
+        // a "failed to write" error later.
+        if flushed == 0 {
+            return Ok(0);
+        }
+
+        // Now that the write has succeeded, buffer the rest (or as much of
+        // the rest as possible). If there were any unwritten newlines, we
+        // only buffer out to the last unwritten newline that fits in the
+        // buffer; this helps prevent flushing partial lines on subsequent
+        // calls to LineWriter::write.
+        //
+        // Handle the cases in order of most-common to least-common, under
+        // the presumption that most writes succeed in totality, and that most
+        // writes are smaller than the buffer.
+        // - Is this a partial line (ie, no newlines left in the unwritten tail)
+        // - If not, does the data out to the last unwritten newline fit in
+        //   the buffer?
+        // - If not, scan for the last newline that *does* fit in the buffer
+        let tail = if flushed >= newline_idx {
+            &buf[flushed..]
+        } else if newline_idx - flushed <= self.inner.capacity() {
+            &buf[flushed..newline_idx]
+        } else {
+            let scan_area = &buf[flushed..];
+            let scan_area = &scan_area[..self.inner.capacity()];
+            match scan_area.iter().rposition(|&b| b == b'\n') {
+                Some(newline_idx) => &scan_area[..newline_idx + 1],
+                None => scan_area,
+            }
+        };
+
+        let buffered = self.inner.write_to_buf(tail);
+        Ok(flushed + buffered)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+
+    /// Writes the entire buffer, flushing through to the inner writer up to
+    /// and including the last newline in `buf`, and buffering the trailing
+    /// partial line.
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        match buf.iter().rposition(|&b| b == b'\n') {
+            // If there are no new newlines (that is, if this write is less
+            // than one line), just do a regular buffered write (which may
+            // flush if we exceed the inner buffer's size)
+            None => {
+                self.flush_if_completed_line()?;
+                self.inner.write_all(buf)
+            }
+            Some(newline_idx) => {
+                let (lines, tail) = buf.split_at(newline_idx + 1);
+
+                if self.inner.buffer().is_empty() {
+                    self.inner.get_mut().write_all(lines)?;
+                } else {
+                    // If there is any buffered data, we add the incoming lines
+                    // to that buffer before flushing, which saves us at least
+                    // one write call. We can't really do this with `write`,
+                    // since we can't do this *and* not suppress errors *and*
+                    // report a consistent state to the caller in a return
+                    // value, but here in write_all it's fine.
+                    self.inner.write_all(lines)?;
+                    self.inner.flush_buf()?;
+                }
+
+                self.inner.write_all(tail)
+            }
+        }
+    }
+}
+
+impl<W: Write + fmt::Debug> fmt::Debug for LineWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LineWriter")
+            .field("writer", self.get_ref())
+            .field(
+                "buffer",
+                &format_args!("{}/{}", self.buffer().len(), self.inner.capacity()),
+            )
+            .finish_non_exhaustive()
+    }
+}
@@ -1,3 +1,75 @@
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::Error;
+
+#[cfg(feature = "alloc")]
+mod linewriter;
 mod reader;
+#[cfg(feature = "alloc")]
+mod writer;
 
+#[cfg(feature = "alloc")]
+pub use self::linewriter::LineWriter;
 pub use self::reader::BufReader;
+#[cfg(feature = "alloc")]
+pub use self::writer::BufWriter;
+
+/// An error returned by [`BufWriter::into_inner`] which combines an error that
+/// happened while writing out the buffer, and the buffered writer object
+/// which may be used to recover from the condition.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct IntoInnerError<W>(W, Error);
+
+#[cfg(feature = "alloc")]
+impl<W> IntoInnerError<W> {
+    /// Constructs a new `IntoInnerError`.
+    fn new(writer: W, error: Error) -> Self {
+        Self(writer, error)
+    }
+
+    /// Helper to construct a new `IntoInnerError`; intended to help with
+    /// adapters that wrap other adapters.
+    fn new_wrapped<W2>(self, f: impl FnOnce(W) -> W2) -> IntoInnerError<W2> {
+        let Self(writer, error) = self;
+        IntoInnerError::new(f(writer), error)
+    }
+
+    /// Returns the error which caused the call to [`BufWriter::into_inner`]
+    /// to fail.
+    pub fn error(&self) -> &Error {
+        &self.1
+    }
+
+    /// Returns the buffered writer instance which generated the error.
+    ///
+    /// The returned object can be used for error recovery, such as
+    /// re-inspecting the buffer.
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+
+    /// Consumes the [`IntoInnerError`] and returns the error which caused the
+    /// call to [`BufWriter::into_inner`] to fail. Unlike [`error`], this can
+    /// be used to obtain ownership of the underlying error.
+    ///
+    /// [`error`]: IntoInnerError::error
+    pub fn into_error(self) -> Error {
+        self.1
+    }
+
+    /// Consumes the [`IntoInnerError`] and returns the error which caused the
+    /// call to [`BufWriter::into_inner`] to fail, and the underlying writer.
+    pub fn into_parts(self) -> (Error, W) {
+        (self.1, self.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<W> fmt::Display for IntoInnerError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error().fmt(f)
+    }
+}
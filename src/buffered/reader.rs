@@ -1,33 +1,114 @@
-use core::mem::MaybeUninit;
+use core::{io::BorrowedBuf, mem::MaybeUninit, task::Context};
 
-use crate::{BufRead, Read, Result};
+use crate::{
+    BorrowedCursor, Buf, BufRead, IoEvents, IoSliceMut, Pollable, Read, Result, Seek, SeekFrom,
+};
 
 #[cfg(feature = "alloc")]
-use alloc::{string::String, vec::Vec};
+use alloc::{boxed::Box, string::String, vec::Vec};
 
 const DEFAULT_BUF_SIZE: usize = 1024;
 
 /// The `BufReader<R>` struct adds buffering to any reader.
-pub struct BufReader<R> {
+///
+/// By default the buffer is a 1 KB array stored inline. The inline capacity
+/// can be chosen with the `N` parameter (see [`BufReader::new_inline`]), or,
+/// with the `alloc` feature, the buffer can be allocated on the heap with
+/// [`BufReader::with_capacity`], which is useful when the reader lives on a
+/// small stack.
+pub struct BufReader<R, const N: usize = DEFAULT_BUF_SIZE> {
     inner: R,
     pos: usize,
     filled: usize,
-    buf: [MaybeUninit<u8>; DEFAULT_BUF_SIZE],
+    /// Number of bytes at the start of `buf` known to be initialized, so that
+    /// refills do not have to zero them again.
+    initialized: usize,
+    buf: Buffer<N>,
+}
+
+/// Backing storage of a [`BufReader`].
+enum Buffer<const N: usize> {
+    Inline([MaybeUninit<u8>; N]),
+    #[cfg(feature = "alloc")]
+    Heap(Box<[MaybeUninit<u8>]>),
+}
+
+impl<const N: usize> Buffer<N> {
+    fn as_slice(&self) -> &[MaybeUninit<u8>] {
+        match self {
+            Buffer::Inline(buf) => buf,
+            #[cfg(feature = "alloc")]
+            Buffer::Heap(buf) => buf,
+        }
+    }
+
+    const fn capacity(&self) -> usize {
+        match self {
+            Buffer::Inline(_) => N,
+            #[cfg(feature = "alloc")]
+            Buffer::Heap(buf) => buf.len(),
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [MaybeUninit<u8>] {
+        match self {
+            Buffer::Inline(buf) => buf,
+            #[cfg(feature = "alloc")]
+            Buffer::Heap(buf) => buf,
+        }
+    }
 }
 
 impl<R: Read> BufReader<R> {
     /// Creates a new `BufReader<R>` with a default buffer capacity (1 KB).
     pub const fn new(inner: R) -> BufReader<R> {
-        Self {
+        BufReader::new_inline(inner)
+    }
+}
+
+impl<R: Read, const N: usize> BufReader<R, N> {
+    /// Creates a new `BufReader<R, N>` with an inline buffer of `N` bytes.
+    ///
+    /// The buffer is stored directly in the `BufReader`, so no allocation is
+    /// performed, but the whole struct is at least `N` bytes large.
+    ///
+    /// `N` must not be zero; this is checked at compile time.
+    pub const fn new_inline(inner: R) -> BufReader<R, N> {
+        const { assert!(N > 0, "inline buffer of a BufReader must not be empty") };
+        BufReader {
+            inner,
+            pos: 0,
+            filled: 0,
+            initialized: 0,
+            buf: Buffer::Inline([const { MaybeUninit::uninit() }; N]),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Read> BufReader<R, 0> {
+    /// Creates a new `BufReader<R>` with a heap-allocated buffer of the
+    /// specified capacity.
+    ///
+    /// The returned reader has no inline storage (`N` is `0`), so it stays
+    /// small regardless of `capacity`.
+    ///
+    /// A `capacity` of `0` is rounded up to `1`: an empty buffer could never
+    /// be filled, and [`fill_buf`] would report EOF forever.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    pub fn with_capacity(capacity: usize, inner: R) -> BufReader<R, 0> {
+        BufReader {
             inner,
             pos: 0,
             filled: 0,
-            buf: [const { MaybeUninit::uninit() }; DEFAULT_BUF_SIZE],
+            initialized: 0,
+            buf: Buffer::Heap(Box::new_uninit_slice(capacity.max(1))),
         }
     }
 }
 
-impl<R> BufReader<R> {
+impl<R, const N: usize> BufReader<R, N> {
     /// Gets a reference to the underlying reader.
     pub const fn get_ref(&self) -> &R {
         &self.inner
@@ -44,12 +125,12 @@ impl<R> BufReader<R> {
     ///
     /// [`fill_buf`]: BufRead::fill_buf
     pub fn buffer(&self) -> &[u8] {
-        unsafe { self.buf[self.pos..self.filled].assume_init_ref() }
+        unsafe { self.buf.as_slice()[self.pos..self.filled].assume_init_ref() }
     }
 
     /// Returns the number of bytes the internal buffer can hold at once.
     pub const fn capacity(&self) -> usize {
-        DEFAULT_BUF_SIZE
+        self.buf.capacity()
     }
 
     /// Unwraps this `BufReader<R>`, returning the underlying reader.
@@ -67,7 +148,31 @@ impl<R> BufReader<R> {
     }
 }
 
-impl<R: Read> Read for BufReader<R> {
+impl<R: Seek, const N: usize> BufReader<R, N> {
+    /// Seeks relative to the current position. If the new position lies within
+    /// the buffer, the buffer will not be flushed, allowing for more efficient
+    /// seeks. This method does not return the location of the underlying
+    /// reader, so the caller must track this information themselves if it is
+    /// required.
+    pub fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        let pos = self.pos as u64;
+        if offset < 0 {
+            if pos.checked_sub(offset.unsigned_abs()).is_some() {
+                self.pos -= offset.unsigned_abs() as usize;
+                return Ok(());
+            }
+        } else if let Some(new_pos) = pos.checked_add(offset as u64) {
+            if new_pos <= self.filled as u64 {
+                self.pos = new_pos as usize;
+                return Ok(());
+            }
+        }
+
+        self.seek(SeekFrom::Current(offset)).map(drop)
+    }
+}
+
+impl<R: Read, const N: usize> Read for BufReader<R, N> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         // If we don't have any buffered data and we're doing a massive read
         // (larger than our internal buffer), bypass our internal buffer
@@ -84,6 +189,42 @@ impl<R: Read> Read for BufReader<R> {
         Ok(nread)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let total_len = bufs.iter().map(|b| b.len()).sum::<usize>();
+        if self.is_empty() && total_len >= self.capacity() {
+            self.discard_buffer();
+            return self.inner.read_vectored(bufs);
+        }
+        let nread = {
+            let mut rem = self.fill_buf()?;
+            rem.read_vectored(bufs)?
+        };
+        self.consume(nread);
+        Ok(nread)
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        // If we don't have any buffered data and we're doing a massive read
+        // (larger than our internal buffer), bypass our internal buffer
+        // entirely.
+        if self.is_empty() && cursor.capacity() >= self.capacity() {
+            self.discard_buffer();
+            return self.inner.read_buf(cursor);
+        }
+
+        let prev = cursor.written();
+
+        let mut rem = self.fill_buf()?;
+        rem.read_buf(cursor.reborrow())?; // infallible
+
+        self.consume(cursor.written() - prev); // slice impl of read_buf known to never unfill buf
+        Ok(())
+    }
+
     // Small read_exacts from a BufReader are extremely common when used with a deserializer.
     // The default implementation calls read in a loop, which results in surprisingly poor code
     // generation for the common path where the buffer has enough bytes to fill the passed-in
@@ -95,7 +236,20 @@ impl<R: Read> Read for BufReader<R> {
             self.pos += amt;
             return Ok(());
         }
-        self.inner.read_exact(buf)
+        // The buffer holds only part of the request (or nothing). Go through
+        // our own `read` in a loop so that the buffered bytes are drained
+        // first and the rest is read with the usual buffering/bypass logic.
+        crate::default_read_exact(self, buf)
+    }
+
+    fn read_buf_exact(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        if self.buffer().len() >= cursor.capacity() {
+            let amt = cursor.capacity();
+            cursor.append(&self.buffer()[..amt]);
+            self.pos += amt;
+            return Ok(());
+        }
+        crate::default_read_buf_exact(self, cursor)
     }
 
     // The inner reader might have an optimized `read_to_end`. Drain our buffer and then
@@ -145,12 +299,19 @@ impl<R: Read> Read for BufReader<R> {
     }
 }
 
-impl<R: Read> BufRead for BufReader<R> {
+impl<R: Read, const N: usize> BufRead for BufReader<R, N> {
     fn fill_buf(&mut self) -> Result<&[u8]> {
         if self.is_empty() {
-            let read_len = self.inner.read(unsafe { self.buf.assume_init_mut() })?;
+            let mut buf = BorrowedBuf::from(self.buf.as_mut_slice());
+            // SAFETY: `self.initialized` bytes will always have been
+            // initialized by a previous refill.
+            unsafe { buf.set_init(self.initialized) };
+
+            let result = self.inner.read_buf(buf.unfilled());
             self.pos = 0;
-            self.filled = read_len;
+            self.filled = buf.len();
+            self.initialized = buf.init_len();
+            result?;
         }
         Ok(self.buffer())
     }
@@ -159,3 +320,108 @@ impl<R: Read> BufRead for BufReader<R> {
         self.pos = core::cmp::min(self.pos + amt, self.filled);
     }
 }
+
+impl<R: Seek, const N: usize> Seek for BufReader<R, N> {
+    /// Seek to an offset, in bytes, in the underlying reader.
+    ///
+    /// The position used for seeking with <code>[SeekFrom::Current]\(_)</code>
+    /// is the position the underlying reader would be at if the `BufReader<R>`
+    /// had no internal buffer.
+    ///
+    /// Seeking always discards the internal buffer, even if the seek position
+    /// would otherwise fall within it. To seek within the buffer without
+    /// discarding it, use [`BufReader::seek_relative`].
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let result: u64;
+        if let SeekFrom::Current(n) = pos {
+            let remainder = (self.filled - self.pos) as i64;
+            // it should be safe to assume that remainder fits within an i64 as
+            // the alternative means we managed to allocate 8 exbibytes and
+            // that's absurd. But it's not out of the realm of possibility for
+            // some weird underlying reader to support seeking by i64::MIN so
+            // we need to handle underflow when subtracting remainder.
+            if let Some(offset) = n.checked_sub(remainder) {
+                result = self.inner.seek(SeekFrom::Current(offset))?;
+            } else {
+                // seek backwards by our remainder, and then by the offset
+                self.inner.seek(SeekFrom::Current(-remainder))?;
+                self.discard_buffer();
+                result = self.inner.seek(SeekFrom::Current(n))?;
+            }
+        } else {
+            // Seeking with Start/End doesn't care about our buffer length.
+            result = self.inner.seek(pos)?;
+        }
+        self.discard_buffer();
+        Ok(result)
+    }
+
+    /// Seeks relative to the current position, keeping the internal buffer if
+    /// the new position lies within it. See [`BufReader::seek_relative`].
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        BufReader::seek_relative(self, offset)
+    }
+
+    /// Returns the current seek position from the start of the stream.
+    ///
+    /// The value returned is equivalent to `self.seek(SeekFrom::Current(0))`
+    /// but does not flush the internal buffer.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the position of the inner reader is smaller
+    /// than the amount of buffered data. That can happen if the inner reader
+    /// has an incorrect implementation of [`Seek::stream_position`], or if the
+    /// position has gone out of sync due to calling [`Seek::seek`] directly on
+    /// the underlying reader.
+    fn stream_position(&mut self) -> Result<u64> {
+        let remainder = (self.filled - self.pos) as u64;
+        self.inner.stream_position().map(|pos| {
+            pos.checked_sub(remainder).expect(
+                "overflow when subtracting remaining buffer size from inner stream position",
+            )
+        })
+    }
+}
+
+/// A `BufReader` is readable whenever it still has buffered data, regardless
+/// of the state of the inner reader.
+impl<R: Pollable, const N: usize> Pollable for BufReader<R, N> {
+    fn poll(&self) -> IoEvents {
+        let mut events = self.inner.poll();
+        if !self.is_empty() {
+            events |= IoEvents::IN | IoEvents::RDNORM;
+        }
+        events
+    }
+
+    fn register(&self, context: &mut Context<'_>, events: IoEvents) {
+        self.inner.register(context, events)
+    }
+}
+
+/// A `BufReader` over a [`Buf`] is itself a `Buf`: buffered bytes come first,
+/// followed by the bytes remaining in the inner buffer.
+impl<R: Buf, const N: usize> Buf for BufReader<R, N> {
+    fn remaining(&self) -> usize {
+        (self.filled - self.pos).saturating_add(self.inner.remaining())
+    }
+
+    fn chunk(&self) -> &[u8] {
+        if self.is_empty() {
+            self.inner.chunk()
+        } else {
+            self.buffer()
+        }
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        let buffered = self.filled - self.pos;
+        if cnt <= buffered {
+            self.pos += cnt;
+        } else {
+            self.discard_buffer();
+            self.inner.advance(cnt - buffered);
+        }
+    }
+}
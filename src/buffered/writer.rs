@@ -0,0 +1,309 @@
+use core::{fmt, mem::ManuallyDrop, ptr};
+
+use alloc::vec::Vec;
+use axerrno::ax_err;
+
+use super::IntoInnerError;
+use crate::{IoSlice, Result, Seek, SeekFrom, Write, DEFAULT_BUF_SIZE};
+
+/// Wraps a writer and buffers its output.
+///
+/// It can be excessively inefficient to work directly with something that
+/// implements [`Write`]. For example, every call to [`write`][Write::write] on
+/// a console results in a separate device access. A `BufWriter<W>` keeps an
+/// in-memory buffer of data and writes it to an underlying writer in large,
+/// infrequent batches.
+///
+/// The buffer will be written out when the writer is dropped, and any error
+/// during that final flush is ignored. Call [`flush`][Write::flush] or
+/// [`into_inner`][BufWriter::into_inner] before dropping to observe it.
+pub struct BufWriter<W: Write> {
+    inner: W,
+    buf: Vec<u8>,
+    // If the inner writer panics in a call to write, we don't want to
+    // write the buffered data a second time in BufWriter's destructor. This
+    // flag tells the Drop impl if it should skip the flush.
+    panicked: bool,
+}
+
+impl<W: Write> BufWriter<W> {
+    /// Creates a new `BufWriter<W>` with a default buffer capacity (1 KB).
+    pub fn new(inner: W) -> BufWriter<W> {
+        BufWriter::with_capacity(DEFAULT_BUF_SIZE, inner)
+    }
+
+    /// Creates a new `BufWriter<W>` with at least the specified buffer
+    /// capacity.
+    pub fn with_capacity(capacity: usize, inner: W) -> BufWriter<W> {
+        BufWriter {
+            inner,
+            buf: Vec::with_capacity(capacity),
+            panicked: false,
+        }
+    }
+
+    /// Send data in our local buffer into the inner writer, looping as
+    /// necessary until either it's all been sent or an error occurs.
+    ///
+    /// Bytes which have been accepted by the inner writer are removed from
+    /// the buffer even if a later write fails, so a retry never duplicates
+    /// data.
+    pub(super) fn flush_buf(&mut self) -> Result<()> {
+        let mut written = 0;
+        let mut ret = Ok(());
+        while written < self.buf.len() {
+            self.panicked = true;
+            let r = self.inner.write(&self.buf[written..]);
+            self.panicked = false;
+
+            match r {
+                Ok(0) => {
+                    ret = ax_err!(Io, "failed to write the buffered data");
+                    break;
+                }
+                Ok(n) => written += n,
+                Err(e) => {
+                    ret = Err(e);
+                    break;
+                }
+            }
+        }
+        if written > 0 {
+            self.buf.drain(..written);
+        }
+        ret
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the internally buffered data.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Returns the number of bytes the internal buffer can hold without
+    /// flushing.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Unwraps this `BufWriter<W>`, returning the underlying writer.
+    ///
+    /// The buffer is written out before returning the writer.
+    ///
+    /// # Errors
+    ///
+    /// An [`Err`] will be returned if an error occurs while flushing the
+    /// buffer. The returned [`IntoInnerError`] gives back this `BufWriter`,
+    /// so neither the writer nor the unflushed data is lost; use
+    /// [`into_parts`][BufWriter::into_parts] to take both apart.
+    pub fn into_inner(mut self) -> core::result::Result<W, IntoInnerError<BufWriter<W>>> {
+        match self.flush_buf() {
+            Err(e) => Err(IntoInnerError::new(self, e)),
+            Ok(()) => Ok(self.into_parts().0),
+        }
+    }
+
+    /// Disassembles this `BufWriter<W>`, returning the underlying writer, and
+    /// any buffered but unwritten data.
+    ///
+    /// Unlike [`into_inner`][BufWriter::into_inner], this does not attempt to
+    /// flush the buffer.
+    pub fn into_parts(self) -> (W, Vec<u8>) {
+        let mut this = ManuallyDrop::new(self);
+        let buf = core::mem::take(&mut this.buf);
+        // SAFETY: double-drops are prevented by putting `this` in a
+        // ManuallyDrop that is never dropped.
+        let inner = unsafe { ptr::read(&this.inner) };
+        (inner, buf)
+    }
+
+    #[inline]
+    fn spare_capacity(&self) -> usize {
+        self.buf.capacity() - self.buf.len()
+    }
+
+    /// Buffer some data without flushing it, regardless of the size of the
+    /// data. Writes as much as possible without exceeding capacity. Returns
+    /// the number of bytes written.
+    pub(super) fn write_to_buf(&mut self, buf: &[u8]) -> usize {
+        let amt = buf.len().min(self.spare_capacity());
+        self.buf.extend_from_slice(&buf[..amt]);
+        amt
+    }
+
+    // Slow path of `write`: the data does not fit into the spare capacity, so
+    // we either make room by flushing, or bypass the buffer entirely when the
+    // data is at least as large as the buffer itself.
+    #[cold]
+    #[inline(never)]
+    fn write_cold(&mut self, buf: &[u8]) -> Result<usize> {
+        if buf.len() > self.spare_capacity() {
+            self.flush_buf()?;
+        }
+        if buf.len() >= self.buf.capacity() {
+            self.panicked = true;
+            let r = self.inner.write(buf);
+            self.panicked = false;
+            r
+        } else {
+            self.buf.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn write_all_cold(&mut self, buf: &[u8]) -> Result<()> {
+        if buf.len() > self.spare_capacity() {
+            self.flush_buf()?;
+        }
+        if buf.len() >= self.buf.capacity() {
+            self.panicked = true;
+            let r = self.inner.write_all(buf);
+            self.panicked = false;
+            r
+        } else {
+            self.buf.extend_from_slice(buf);
+            Ok(())
+        }
+    }
+}
+
+impl<W: Write> Write for BufWriter<W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        // Use < instead of <= to avoid a needless trip through the buffer in
+        // some cases. See `write_cold` for details.
+        if buf.len() < self.spare_capacity() {
+            self.buf.extend_from_slice(buf);
+            Ok(buf.len())
+        } else {
+            self.write_cold(buf)
+        }
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        if self.inner.is_write_vectored() {
+            let saturated_total_len = bufs
+                .iter()
+                .fold(0usize, |acc, b| acc.saturating_add(b.len()));
+
+            if saturated_total_len > self.spare_capacity() {
+                // Flush if the total length of the input exceeds our buffer's
+                // spare capacity.
+                self.flush_buf()?;
+            }
+            if saturated_total_len >= self.buf.capacity() {
+                // Forward to our inner writer if the total length of the input
+                // is greater than or equal to our buffer capacity.
+                self.panicked = true;
+                let r = self.inner.write_vectored(bufs);
+                self.panicked = false;
+                r
+            } else {
+                // The input fits in the spare capacity now.
+                bufs.iter().for_each(|b| self.buf.extend_from_slice(b));
+                Ok(saturated_total_len)
+            }
+        } else {
+            let mut iter = bufs.iter();
+            let mut total_written = if let Some(buf) = iter.by_ref().find(|&buf| !buf.is_empty()) {
+                // This is the first non-empty slice to write, so if it does
+                // not fit in the buffer, we still get to flush and proceed.
+                if buf.len() > self.spare_capacity() {
+                    self.flush_buf()?;
+                }
+                if buf.len() >= self.buf.capacity() {
+                    // The slice is at least as large as the buffering capacity,
+                    // so it's better to write it directly, bypassing the buffer.
+                    self.panicked = true;
+                    let r = self.inner.write(buf);
+                    self.panicked = false;
+                    return r;
+                } else {
+                    self.buf.extend_from_slice(buf);
+                    buf.len()
+                }
+            } else {
+                return Ok(0);
+            };
+            debug_assert!(total_written != 0);
+            for buf in iter {
+                if buf.len() <= self.spare_capacity() {
+                    self.buf.extend_from_slice(buf);
+                    total_written += buf.len();
+                } else {
+                    // Stop at the first slice that does not fit, so that the
+                    // caller never sees a partially buffered slice.
+                    break;
+                }
+            }
+            Ok(total_written)
+        }
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        // Normally, `write_all` just calls `write` in a loop. We can do better
+        // by calling `self.inner.write_all()` directly, which avoids
+        // round trips through the buffer in the event of a series of partial
+        // writes in some circumstances.
+        if buf.len() < self.spare_capacity() {
+            self.buf.extend_from_slice(buf);
+            Ok(())
+        } else {
+            self.write_all_cold(buf)
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.flush_buf()?;
+        self.inner.flush()
+    }
+}
+
+impl<W: Write + Seek> Seek for BufWriter<W> {
+    /// Seek to the offset, in bytes, in the underlying writer.
+    ///
+    /// Seeking always writes out the internal buffer before seeking.
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.flush_buf()?;
+        self.inner.seek(pos)
+    }
+}
+
+impl<W: Write + fmt::Debug> fmt::Debug for BufWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufWriter")
+            .field("writer", &self.inner)
+            .field(
+                "buffer",
+                &format_args!("{}/{}", self.buf.len(), self.buf.capacity()),
+            )
+            .finish()
+    }
+}
+
+impl<W: Write> Drop for BufWriter<W> {
+    fn drop(&mut self) {
+        if !self.panicked {
+            // dtors should not panic, so we ignore a failed flush
+            let _r = self.flush_buf();
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use axerrno::ax_bail;
+
+use crate::{BufRead, Error, Read, Result, Write};
+
+/// A streaming checksum algorithm.
+///
+/// Data is fed in arbitrary pieces with [`update`]; the result over
+/// everything fed so far is available at any time with [`finish`].
+///
+/// [`update`]: Checksum::update
+/// [`finish`]: Checksum::finish
+pub trait Checksum {
+    /// The type of the checksum value.
+    type Output: Copy + PartialEq;
+
+    /// Feeds `data` into the checksum.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the checksum of all data fed so far.
+    fn finish(&self) -> Self::Output;
+
+    /// Resets the checksum to its initial state.
+    fn reset(&mut self);
+
+    /// Checks the checksum of all data fed so far against `expected`.
+    ///
+    /// Fails with [`InvalidData`] on mismatch.
+    ///
+    /// [`InvalidData`]: crate::Error::InvalidData
+    fn verify(&self, expected: Self::Output) -> Result {
+        if self.finish() != expected {
+            ax_bail!(InvalidData, "checksum mismatch");
+        }
+        Ok(())
+    }
+}
+
+/// Builds the lookup table of a reflected CRC-32 with polynomial `poly`.
+const fn crc32_table(poly: u32) -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ poly
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+fn crc32_update(table: &[u32; 256], mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc = table[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+static CRC32_TABLE: [u32; 256] = crc32_table(0xedb8_8320);
+static CRC32C_TABLE: [u32; 256] = crc32_table(0x82f6_3b78);
+
+/// CRC-32 (IEEE 802.3), as used by Ethernet, gzip, zip and PNG.
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32(u32);
+
+impl Crc32 {
+    /// Creates a new CRC-32 with no data fed.
+    pub const fn new() -> Crc32 {
+        Crc32(!0)
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Crc32::new()
+    }
+}
+
+impl Checksum for Crc32 {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        self.0 = crc32_update(&CRC32_TABLE, self.0, data);
+    }
+
+    fn finish(&self) -> u32 {
+        !self.0
+    }
+
+    fn reset(&mut self) {
+        *self = Crc32::new();
+    }
+}
+
+/// CRC-32C (Castagnoli), as used by iSCSI, SCTP, ext4 and btrfs.
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32c(u32);
+
+impl Crc32c {
+    /// Creates a new CRC-32C with no data fed.
+    pub const fn new() -> Crc32c {
+        Crc32c(!0)
+    }
+}
+
+impl Default for Crc32c {
+    fn default() -> Self {
+        Crc32c::new()
+    }
+}
+
+impl Checksum for Crc32c {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        self.0 = crc32_update(&CRC32C_TABLE, self.0, data);
+    }
+
+    fn finish(&self) -> u32 {
+        !self.0
+    }
+
+    fn reset(&mut self) {
+        *self = Crc32c::new();
+    }
+}
+
+/// Adler-32, as used by zlib.
+#[derive(Debug, Clone, Copy)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    const MOD: u32 = 65521;
+    /// Largest number of bytes that can be summed before `b` may overflow.
+    const NMAX: usize = 5552;
+
+    /// Creates a new Adler-32 with no data fed.
+    pub const fn new() -> Adler32 {
+        Adler32 { a: 1, b: 0 }
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Adler32::new()
+    }
+}
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        for chunk in data.chunks(Self::NMAX) {
+            for &byte in chunk {
+                self.a += byte as u32;
+                self.b += self.a;
+            }
+            self.a %= Self::MOD;
+            self.b %= Self::MOD;
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+
+    fn reset(&mut self) {
+        *self = Adler32::new();
+    }
+}
+
+/// A reader that computes a checksum of all data read through it.
+///
+/// If the inner reader implements [`BufRead`], so does `ChecksumReader`:
+/// bytes are added to the checksum when they are [`consume`]d, taken from the
+/// buffer of the inner reader, which still holds them at that point.
+///
+/// [`consume`]: BufRead::consume
+#[derive(Debug)]
+pub struct ChecksumReader<R, C> {
+    inner: R,
+    checksum: C,
+    /// The first error that kept consumed bytes out of the checksum.
+    error: Option<Error>,
+}
+
+impl<R, C: Checksum> ChecksumReader<R, C> {
+    /// Creates a new `ChecksumReader` feeding `checksum` with the data read
+    /// from `inner`.
+    pub const fn new(inner: R, checksum: C) -> ChecksumReader<R, C> {
+        ChecksumReader {
+            inner,
+            checksum,
+            error: None,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Data read directly from the underlying reader is not checksummed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the checksum of all data read so far.
+    ///
+    /// Fails with the error that prevented some consumed bytes from being
+    /// checksummed, if any (see [`consume`]).
+    ///
+    /// [`consume`]: BufRead::consume
+    pub fn checksum(&self) -> Result<C::Output> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.checksum.finish()),
+        }
+    }
+
+    /// Checks the checksum of all data read so far against `expected`.
+    ///
+    /// Fails with [`InvalidData`] on mismatch, or with the same error as
+    /// [`checksum`] if the checksum is incomplete.
+    ///
+    /// [`InvalidData`]: crate::Error::InvalidData
+    /// [`checksum`]: ChecksumReader::checksum
+    pub fn verify(&self, expected: C::Output) -> Result {
+        self.checksum()?;
+        self.checksum.verify(expected)
+    }
+
+    /// Unwraps this `ChecksumReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Unwraps this `ChecksumReader`, returning the underlying reader and the
+    /// checksum state.
+    pub fn into_parts(self) -> (R, C) {
+        (self.inner, self.checksum)
+    }
+}
+
+impl<R: Read, C: Checksum> Read for ChecksumReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.checksum.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead, C: Checksum> BufRead for ChecksumReader<R, C> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    /// Consumes `amt` bytes, adding them to the checksum.
+    ///
+    /// The bytes are read from the buffer of the underlying reader, fetched
+    /// again with [`fill_buf`]; the bytes being consumed are still buffered,
+    /// so this does not read anything new. If that fails, or the buffer is
+    /// shorter than `amt`, the bytes are consumed without being checksummed
+    /// and the error is reported by [`checksum`] and [`verify`].
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    /// [`checksum`]: ChecksumReader::checksum
+    /// [`verify`]: ChecksumReader::verify
+    fn consume(&mut self, amt: usize) {
+        if amt == 0 {
+            return;
+        }
+        match self.inner.fill_buf() {
+            Ok(buf) if amt <= buf.len() => self.checksum.update(&buf[..amt]),
+            Ok(_) => {
+                self.error.get_or_insert(Error::InvalidInput);
+            }
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
+        self.inner.consume(amt)
+    }
+}
+
+/// A writer that computes a checksum of all data written through it.
+///
+/// Only the bytes accepted by the inner writer are checksummed.
+#[derive(Debug)]
+pub struct ChecksumWriter<W, C> {
+    inner: W,
+    checksum: C,
+}
+
+impl<W, C: Checksum> ChecksumWriter<W, C> {
+    /// Creates a new `ChecksumWriter` feeding `checksum` with the data written
+    /// to `inner`.
+    pub const fn new(inner: W, checksum: C) -> ChecksumWriter<W, C> {
+        ChecksumWriter { inner, checksum }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Data written directly to the underlying writer is not checksummed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the checksum of all data written so far.
+    pub fn checksum(&self) -> C::Output {
+        self.checksum.finish()
+    }
+
+    /// Checks the checksum of all data written so far against `expected`.
+    ///
+    /// Fails with [`InvalidData`] on mismatch.
+    ///
+    /// [`InvalidData`]: crate::Error::InvalidData
+    pub fn verify(&self, expected: C::Output) -> Result {
+        self.checksum.verify(expected)
+    }
+
+    /// Unwraps this `ChecksumWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Unwraps this `ChecksumWriter`, returning the underlying writer and the
+    /// checksum state.
+    pub fn into_parts(self) -> (W, C) {
+        (self.inner, self.checksum)
+    }
+}
+
+impl<W: Write, C: Checksum> Write for ChecksumWriter<W, C> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.checksum.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result {
+        self.inner.flush()
+    }
+}
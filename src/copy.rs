@@ -0,0 +1,137 @@
+use core::{io::BorrowedBuf, mem::MaybeUninit};
+
+use axerrno::ax_bail;
+
+use crate::{BufRead, Read, Result, Write, DEFAULT_BUF_SIZE};
+
+/// Copies the entire contents of a reader into a writer.
+///
+/// This function will continuously read data from `reader` and then write it
+/// into `writer` in a streaming fashion until `reader` returns EOF.
+///
+/// On success, the total number of bytes that were copied from `reader` to
+/// `writer` is returned.
+///
+/// Data is staged through a small (1 KB) buffer on the stack. Use
+/// [`copy_with_buf`] to choose the size and location of that buffer.
+///
+/// `copy` always goes through that buffer, even if the reader implements
+/// [`BufRead`]: telling the two cases apart would need specialization, which
+/// is not available for arbitrary `BufRead` types. Call [`copy_buf`] to copy
+/// straight out of the reader's own buffer instead.
+///
+/// # Errors
+///
+/// This function will return an error immediately if any call to [`read`] or
+/// [`write`] returns an error. Short writes are retried until the whole chunk
+/// has been written.
+///
+/// [`read`]: Read::read
+/// [`write`]: Write::write
+pub fn copy<R, W>(reader: &mut R, writer: &mut W) -> Result<u64>
+where
+    R: Read + ?Sized,
+    W: Write + ?Sized,
+{
+    let mut buf = [MaybeUninit::uninit(); DEFAULT_BUF_SIZE];
+    copy_with_borrowed_buf(reader, writer, &mut BorrowedBuf::from(&mut buf[..]))
+}
+
+/// Copies the entire contents of a reader into a writer, staging data through
+/// the caller-provided `buf`.
+///
+/// This behaves like [`copy`], but lets the caller pick the scratch buffer:
+/// a small array when stack space is tight, or a heap or per-CPU buffer that
+/// lives off the stack entirely.
+///
+/// ```
+/// # fn main() -> axio::Result<()> {
+/// let mut reader: &[u8] = b"hello";
+/// let mut out = [0; 11];
+/// let mut writer = &mut out[..];
+///
+/// // 64 bytes on the stack.
+/// axio::copy_with_buf(&mut reader, &mut writer, &mut [0; 64])?;
+///
+/// // Nothing on the stack.
+/// let mut scratch = vec![0; 64 * 1024];
+/// axio::copy_with_buf(&mut &b" world"[..], &mut writer, &mut scratch)?;
+///
+/// assert_eq!(&out, b"hello world");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Fails with [`InvalidInput`] if `buf` is empty. Otherwise, errors are
+/// reported the same way as by [`copy`].
+///
+/// [`InvalidInput`]: crate::Error::InvalidInput
+pub fn copy_with_buf<R, W>(reader: &mut R, writer: &mut W, buf: &mut [u8]) -> Result<u64>
+where
+    R: Read + ?Sized,
+    W: Write + ?Sized,
+{
+    if buf.is_empty() {
+        ax_bail!(InvalidInput, "empty copy buffer");
+    }
+    copy_with_borrowed_buf(reader, writer, &mut BorrowedBuf::from(buf))
+}
+
+/// Shared loop of [`copy`] and [`copy_with_buf`]. The reader fills `buf`
+/// through [`Read::read_buf`], so an uninitialized buffer is only zeroed if
+/// the reader needs it to be.
+fn copy_with_borrowed_buf<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    buf: &mut BorrowedBuf<'_>,
+) -> Result<u64>
+where
+    R: Read + ?Sized,
+    W: Write + ?Sized,
+{
+    let mut len = 0;
+    loop {
+        buf.clear();
+        reader.read_buf(buf.unfilled())?;
+        if buf.len() == 0 {
+            return Ok(len);
+        }
+        writer.write_all(buf.filled())?;
+        len += buf.len() as u64;
+    }
+}
+
+/// Copies the entire contents of a buffered reader into a writer without an
+/// intermediate buffer.
+///
+/// This is the zero-copy counterpart of [`copy`]: data is written straight
+/// out of the slice returned by [`fill_buf`], and only the bytes the writer
+/// actually accepted are [`consume`]d. A short write therefore never loses
+/// data, and the next iteration retries the remainder.
+///
+/// On success, the total number of bytes that were copied from `reader` to
+/// `writer` is returned.
+///
+/// [`fill_buf`]: BufRead::fill_buf
+/// [`consume`]: BufRead::consume
+pub fn copy_buf<R, W>(reader: &mut R, writer: &mut W) -> Result<u64>
+where
+    R: BufRead + ?Sized,
+    W: Write + ?Sized,
+{
+    let mut len = 0;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(len);
+        }
+        let n = writer.write(buf)?;
+        if n == 0 {
+            ax_bail!(Io, "failed to write whole buffer");
+        }
+        reader.consume(n);
+        len += n as u64;
+    }
+}
@@ -0,0 +1,415 @@
+use core::cmp;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{
+    BorrowedCursor, Buf, BufMut, BufRead, IoSlice, IoSliceMut, Read, Result, Seek, SeekFrom, Write,
+};
+
+/// A `Cursor` wraps an in-memory buffer and provides it with a [`Seek`]
+/// implementation.
+///
+/// `Cursor`s are used with in-memory buffers, anything implementing
+/// <code>[AsRef]<\[u8]></code>, to allow them to implement [`Read`] and/or
+/// [`Write`], allowing these buffers to be used anywhere you might use a
+/// reader or writer that does actual I/O.
+///
+/// Writing is supported for `&mut [u8]`, `[u8; N]` and, with the `alloc`
+/// feature, `Vec<u8>`, `&mut Vec<u8>` and `Box<[u8]>`.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct Cursor<T> {
+    inner: T,
+    pos: u64,
+}
+
+impl<T> Cursor<T> {
+    /// Creates a new cursor wrapping the provided underlying in-memory buffer.
+    ///
+    /// Cursor initial position is `0` even if underlying buffer (e.g., [`Vec`])
+    /// is not empty. So writing to cursor starts with overwriting [`Vec`]
+    /// content, not with appending to it.
+    ///
+    /// [`Vec`]: alloc::vec::Vec
+    pub const fn new(inner: T) -> Cursor<T> {
+        Cursor { pos: 0, inner }
+    }
+
+    /// Consumes this cursor, returning the underlying value.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Gets a reference to the underlying value in this cursor.
+    pub const fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying value in this cursor.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying value as it may corrupt this cursor's position.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the current position of this cursor.
+    pub const fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Sets the position of this cursor.
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+}
+
+impl<T: AsRef<[u8]>> Cursor<T> {
+    /// Returns the remaining slice, i.e. the data after the current position.
+    ///
+    /// The slice is empty if the position is at or past the end of the
+    /// underlying buffer.
+    pub fn remaining_slice(&self) -> &[u8] {
+        let inner = self.inner.as_ref();
+        let start = cmp::min(self.pos, inner.len() as u64) as usize;
+        &inner[start..]
+    }
+
+    /// Returns `true` if the remaining slice is empty.
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.inner.as_ref().len() as u64
+    }
+}
+
+impl<T: AsRef<[u8]>> Seek for Cursor<T> {
+    fn seek(&mut self, style: SeekFrom) -> Result<u64> {
+        self.pos = style.resolve(self.pos, self.inner.as_ref().len() as u64)?;
+        Ok(self.pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        Ok(self.inner.as_ref().len() as u64)
+    }
+}
+
+impl<T: AsRef<[u8]>> Read for Cursor<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = Read::read(&mut self.remaining_slice(), buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let n = Read::read_vectored(&mut self.remaining_slice(), bufs)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        true
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let n = buf.len();
+        Read::read_exact(&mut self.remaining_slice(), buf)?;
+        self.pos += n as u64;
+        Ok(())
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let prev_written = cursor.written();
+        Read::read_buf(&mut self.remaining_slice(), cursor.reborrow())?;
+        self.pos += (cursor.written() - prev_written) as u64;
+        Ok(())
+    }
+
+    fn read_buf_exact(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let prev_written = cursor.written();
+        let result = Read::read_buf_exact(&mut self.remaining_slice(), cursor.reborrow());
+        self.pos += (cursor.written() - prev_written) as u64;
+        result
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        let content = self.remaining_slice();
+        let len = content.len();
+        buf.try_reserve(len)
+            .map_err(|e| axerrno::ax_err_type!(NoMemory, e))?;
+        buf.extend_from_slice(content);
+        self.pos += len as u64;
+        Ok(len)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        let content = core::str::from_utf8(self.remaining_slice())
+            .map_err(|_| axerrno::ax_err_type!(InvalidData, "invalid UTF-8 sequence in stream"))?;
+        let len = content.len();
+        buf.try_reserve(len)
+            .map_err(|e| axerrno::ax_err_type!(NoMemory, e))?;
+        buf.push_str(content);
+        self.pos += len as u64;
+        Ok(len)
+    }
+}
+
+impl<T: AsRef<[u8]>> BufRead for Cursor<T> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(self.remaining_slice())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt as u64;
+    }
+}
+
+impl<T: AsRef<[u8]>> Buf for Cursor<T> {
+    fn remaining(&self) -> usize {
+        self.remaining_slice().len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.remaining_slice()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(cnt <= self.remaining(), "cannot advance past the end");
+        self.pos += cnt as u64;
+    }
+}
+
+// Non-resizing write implementation
+#[inline]
+fn slice_write(pos_mut: &mut u64, slice: &mut [u8], buf: &[u8]) -> Result<usize> {
+    let pos = cmp::min(*pos_mut, slice.len() as u64) as usize;
+    let amt = (&mut slice[pos..]).write(buf)?;
+    *pos_mut += amt as u64;
+    Ok(amt)
+}
+
+#[inline]
+fn slice_write_vectored(
+    pos_mut: &mut u64,
+    slice: &mut [u8],
+    bufs: &[IoSlice<'_>],
+) -> Result<usize> {
+    let mut nwritten = 0;
+    for buf in bufs {
+        let n = slice_write(pos_mut, slice, buf)?;
+        nwritten += n;
+        if n < buf.len() {
+            break;
+        }
+    }
+    Ok(nwritten)
+}
+
+/// Resizing write implementation: writing past the end of the vector
+/// zero-fills the gap between the old end and the cursor position.
+#[cfg(feature = "alloc")]
+pub(crate) fn vec_write(pos_mut: &mut u64, vec: &mut Vec<u8>, buf: &[u8]) -> Result<usize> {
+    let pos = usize::try_from(*pos_mut).map_err(|_| {
+        axerrno::ax_err_type!(
+            InvalidInput,
+            "cursor position exceeds maximum possible vector length"
+        )
+    })?;
+    let end = pos.checked_add(buf.len()).ok_or_else(|| {
+        axerrno::ax_err_type!(
+            InvalidInput,
+            "cursor position exceeds maximum possible vector length"
+        )
+    })?;
+
+    if end > vec.len() {
+        vec.try_reserve(end - vec.len())
+            .map_err(|e| axerrno::ax_err_type!(NoMemory, e))?;
+    }
+    // Pad with zeros if the cursor is past the end of the vector.
+    if pos > vec.len() {
+        vec.resize(pos, 0);
+    }
+    // Overwrite the overlapping part, then append the rest.
+    let overlap = cmp::min(vec.len() - pos, buf.len());
+    vec[pos..pos + overlap].copy_from_slice(&buf[..overlap]);
+    vec.extend_from_slice(&buf[overlap..]);
+
+    *pos_mut = end as u64;
+    Ok(buf.len())
+}
+
+#[cfg(feature = "alloc")]
+fn vec_write_vectored(pos_mut: &mut u64, vec: &mut Vec<u8>, bufs: &[IoSlice<'_>]) -> Result<usize> {
+    let mut nwritten = 0;
+    for buf in bufs {
+        nwritten += vec_write(pos_mut, vec, buf)?;
+    }
+    Ok(nwritten)
+}
+
+impl Write for Cursor<&mut [u8]> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        slice_write(&mut self.pos, self.inner, buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        slice_write_vectored(&mut self.pos, self.inner, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Write for Cursor<&mut Vec<u8>> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        vec_write(&mut self.pos, self.inner, buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        vec_write_vectored(&mut self.pos, self.inner, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Write for Cursor<Vec<u8>> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        vec_write(&mut self.pos, &mut self.inner, buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        vec_write_vectored(&mut self.pos, &mut self.inner, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Write for Cursor<Box<[u8]>> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        slice_write(&mut self.pos, &mut self.inner, buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        slice_write_vectored(&mut self.pos, &mut self.inner, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<const N: usize> Write for Cursor<[u8; N]> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        slice_write(&mut self.pos, &mut self.inner, buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        slice_write_vectored(&mut self.pos, &mut self.inner, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// Non-resizing `BufMut` implementation
+#[inline]
+fn slice_chunk_mut(pos: u64, slice: &mut [u8]) -> &mut [u8] {
+    let pos = cmp::min(pos, slice.len() as u64) as usize;
+    &mut slice[pos..]
+}
+
+impl BufMut for Cursor<&mut [u8]> {
+    fn remaining_mut(&self) -> usize {
+        self.remaining_slice().len()
+    }
+
+    fn chunk_mut(&mut self) -> &mut [u8] {
+        slice_chunk_mut(self.pos, self.inner)
+    }
+
+    fn advance_mut(&mut self, cnt: usize) {
+        assert!(cnt <= self.remaining_mut(), "cannot advance past the end");
+        self.pos += cnt as u64;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BufMut for Cursor<Box<[u8]>> {
+    fn remaining_mut(&self) -> usize {
+        self.remaining_slice().len()
+    }
+
+    fn chunk_mut(&mut self) -> &mut [u8] {
+        slice_chunk_mut(self.pos, &mut self.inner)
+    }
+
+    fn advance_mut(&mut self, cnt: usize) {
+        assert!(cnt <= self.remaining_mut(), "cannot advance past the end");
+        self.pos += cnt as u64;
+    }
+}
+
+impl<const N: usize> BufMut for Cursor<[u8; N]> {
+    fn remaining_mut(&self) -> usize {
+        self.remaining_slice().len()
+    }
+
+    fn chunk_mut(&mut self) -> &mut [u8] {
+        slice_chunk_mut(self.pos, &mut self.inner)
+    }
+
+    fn advance_mut(&mut self, cnt: usize) {
+        assert!(cnt <= self.remaining_mut(), "cannot advance past the end");
+        self.pos += cnt as u64;
+    }
+}
@@ -0,0 +1,638 @@
+//! Adapters between the `axio` traits and those of [`embedded_io`] and
+//! [`embedded_io_async`].
+//!
+//! [`ToEmbedded`] covers blocking and async objects alike. In the other
+//! direction, [`FromEmbedded`] adapts blocking objects and
+//! [`FromEmbeddedAsync`] async ones.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::mem;
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axerrno::{ax_err, AxErrorKind};
+use embedded_io::{self as eio, ErrorKind};
+use embedded_io_async as eio_async;
+
+use crate::{
+    async_io::{
+        AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt,
+    },
+    BufRead, Error, Read, Result, Seek, SeekFrom, Write,
+};
+
+/// Returns the [`embedded_io::ErrorKind`] closest to `err`.
+///
+/// Errors with no counterpart, including [`Error::WouldBlock`], map to
+/// [`ErrorKind::Other`].
+pub fn to_embedded_error_kind(err: Error) -> ErrorKind {
+    use AxErrorKind::*;
+
+    let Ok(kind) = AxErrorKind::try_from(err) else {
+        return ErrorKind::Other;
+    };
+    match kind {
+        AddrInUse => ErrorKind::AddrInUse,
+        AlreadyExists => ErrorKind::AlreadyExists,
+        BrokenPipe => ErrorKind::BrokenPipe,
+        ConnectionRefused => ErrorKind::ConnectionRefused,
+        ConnectionReset => ErrorKind::ConnectionReset,
+        Interrupted => ErrorKind::Interrupted,
+        InvalidData => ErrorKind::InvalidData,
+        InvalidInput => ErrorKind::InvalidInput,
+        NoMemory => ErrorKind::OutOfMemory,
+        NotConnected => ErrorKind::NotConnected,
+        NotFound => ErrorKind::NotFound,
+        OperationNotPermitted | PermissionDenied => ErrorKind::PermissionDenied,
+        OperationNotSupported | Unsupported => ErrorKind::Unsupported,
+        TimedOut => ErrorKind::TimedOut,
+        WriteZero => ErrorKind::WriteZero,
+        _ => ErrorKind::Other,
+    }
+}
+
+/// Returns the [`Error`] closest to `kind`.
+///
+/// Kinds with no counterpart map to [`Error::Io`].
+pub fn from_embedded_error_kind(kind: ErrorKind) -> Error {
+    match kind {
+        ErrorKind::AddrInUse => Error::AddrInUse,
+        ErrorKind::AlreadyExists => Error::AlreadyExists,
+        ErrorKind::BrokenPipe => Error::BrokenPipe,
+        ErrorKind::ConnectionAborted | ErrorKind::ConnectionReset => Error::ConnectionReset,
+        ErrorKind::ConnectionRefused => Error::ConnectionRefused,
+        ErrorKind::Interrupted => Error::Interrupted,
+        ErrorKind::InvalidData => Error::InvalidData,
+        ErrorKind::InvalidInput | ErrorKind::AddrNotAvailable => Error::InvalidInput,
+        ErrorKind::NotConnected => Error::NotConnected,
+        ErrorKind::NotFound => Error::NotFound,
+        ErrorKind::OutOfMemory => Error::NoMemory,
+        ErrorKind::PermissionDenied => Error::PermissionDenied,
+        ErrorKind::TimedOut => Error::TimedOut,
+        ErrorKind::Unsupported => Error::Unsupported,
+        ErrorKind::WriteZero => Error::WriteZero,
+        _ => Error::Io,
+    }
+}
+
+fn from_embedded_error<E: eio::Error>(err: E) -> Error {
+    from_embedded_error_kind(err.kind())
+}
+
+fn from_read_exact_error<E: eio::Error>(err: eio::ReadExactError<E>) -> Error {
+    match err {
+        eio::ReadExactError::UnexpectedEof => {
+            axerrno::ax_err_type!(Io, "failed to fill whole buffer")
+        }
+        eio::ReadExactError::Other(e) => from_embedded_error(e),
+    }
+}
+
+/// The error type of [`ToEmbedded`], carrying the original [`Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbeddedError(pub Error);
+
+impl fmt::Display for EmbeddedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl core::error::Error for EmbeddedError {}
+
+impl eio::Error for EmbeddedError {
+    fn kind(&self) -> ErrorKind {
+        to_embedded_error_kind(self.0)
+    }
+}
+
+impl From<Error> for EmbeddedError {
+    fn from(err: Error) -> Self {
+        EmbeddedError(err)
+    }
+}
+
+impl From<SeekFrom> for eio::SeekFrom {
+    fn from(pos: SeekFrom) -> Self {
+        match pos {
+            SeekFrom::Start(n) => eio::SeekFrom::Start(n),
+            SeekFrom::End(n) => eio::SeekFrom::End(n),
+            SeekFrom::Current(n) => eio::SeekFrom::Current(n),
+        }
+    }
+}
+
+impl From<eio::SeekFrom> for SeekFrom {
+    fn from(pos: eio::SeekFrom) -> Self {
+        match pos {
+            eio::SeekFrom::Start(n) => SeekFrom::Start(n),
+            eio::SeekFrom::End(n) => SeekFrom::End(n),
+            eio::SeekFrom::Current(n) => SeekFrom::Current(n),
+        }
+    }
+}
+
+/// An adapter that implements the `axio` traits for an [`embedded_io`]
+/// object.
+///
+/// Errors are converted with [`from_embedded_error_kind`]. For
+/// [`embedded_io_async`] objects, see [`FromEmbeddedAsync`].
+#[derive(Debug, Default, Clone)]
+pub struct FromEmbedded<T> {
+    inner: T,
+}
+
+impl<T> FromEmbedded<T> {
+    /// Wraps an [`embedded_io`] object.
+    pub const fn new(inner: T) -> FromEmbedded<T> {
+        FromEmbedded { inner }
+    }
+
+    /// Gets a reference to the underlying object.
+    pub const fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying object.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps this `FromEmbedded`, returning the underlying object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: eio::Read> Read for FromEmbedded<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.inner.read(buf).map_err(from_embedded_error)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result {
+        self.inner.read_exact(buf).map_err(from_read_exact_error)
+    }
+}
+
+impl<T: eio::Write> Write for FromEmbedded<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.inner.write(buf).map_err(from_embedded_error)
+    }
+
+    fn flush(&mut self) -> Result {
+        self.inner.flush().map_err(from_embedded_error)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result {
+        self.inner.write_all(buf).map_err(from_embedded_error)
+    }
+}
+
+impl<T: eio::Seek> Seek for FromEmbedded<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos.into()).map_err(from_embedded_error)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.inner.stream_position().map_err(from_embedded_error)
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        self.inner
+            .seek_relative(offset)
+            .map_err(from_embedded_error)
+    }
+}
+
+impl<T: eio::BufRead> BufRead for FromEmbedded<T> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.inner.fill_buf().map_err(from_embedded_error)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+/// An operation of [`FromEmbeddedAsync`] in flight. It owns the object and
+/// the buffer it works on, and hands both back when it completes.
+#[cfg(feature = "alloc")]
+type Op<T> = Pin<Box<dyn Future<Output = (T, Box<[u8]>, Result<usize>)>>>;
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OpKind {
+    Read,
+    Write,
+    Flush,
+}
+
+/// An adapter that implements [`AsyncRead`], [`AsyncBufRead`] and
+/// [`AsyncWrite`] for an [`embedded_io_async`] object.
+///
+/// The futures of [`embedded_io_async`] borrow the object and the buffer
+/// passed to them until they complete, while [`AsyncRead::poll_read`] and
+/// [`AsyncWrite::poll_write`] may get a different buffer on every poll. So
+/// `FromEmbeddedAsync` owns the object along with a read buffer and a write
+/// buffer, keeps the future of the operation in flight across polls, and
+/// copies data through its buffers:
+///
+/// - Reads fill the read buffer with one `read` of the object, and are
+///   served from it until it is consumed.
+/// - A write copies as much data as fits into the write buffer and succeeds
+///   right away. The data is written out with `write` in a loop, driven by
+///   the following calls, which also report its errors; use
+///   [`poll_flush`] to wait for it.
+///
+/// Only one operation is in flight at a time: an unfinished operation is
+/// completed before the next one starts, e.g. a write waits for a pending
+/// read. A full-duplex driver should be split into halves wrapped
+/// separately.
+///
+/// Errors are converted with [`from_embedded_error_kind`].
+///
+/// [`poll_flush`]: AsyncWrite::poll_flush
+#[cfg(feature = "alloc")]
+pub struct FromEmbeddedAsync<T> {
+    /// The object, unless an operation is in flight.
+    inner: Option<T>,
+    op: Option<(OpKind, Op<T>)>,
+    read_buf: Box<[u8]>,
+    pos: usize,
+    filled: usize,
+    write_buf: Box<[u8]>,
+}
+
+// The object is moved in and out of the boxed futures and never pinned.
+#[cfg(feature = "alloc")]
+impl<T> Unpin for FromEmbeddedAsync<T> {}
+
+#[cfg(feature = "alloc")]
+impl<T> FromEmbeddedAsync<T> {
+    /// Wraps an [`embedded_io_async`] object, with read and write buffers of
+    /// 1 KiB each.
+    pub fn new(inner: T) -> FromEmbeddedAsync<T> {
+        Self::with_capacity(crate::DEFAULT_BUF_SIZE, inner)
+    }
+
+    /// Wraps an [`embedded_io_async`] object, with read and write buffers of
+    /// `capacity` bytes each.
+    ///
+    /// A `capacity` of zero is rounded up to one.
+    pub fn with_capacity(capacity: usize, inner: T) -> FromEmbeddedAsync<T> {
+        let capacity = capacity.max(1);
+        FromEmbeddedAsync {
+            inner: Some(inner),
+            op: None,
+            read_buf: alloc::vec![0; capacity].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+            write_buf: alloc::vec![0; capacity].into_boxed_slice(),
+        }
+    }
+
+    /// Gets a reference to the underlying object, or `None` while an
+    /// operation is in flight.
+    pub fn get_ref(&self) -> Option<&T> {
+        self.inner.as_ref()
+    }
+
+    /// Gets a mutable reference to the underlying object, or `None` while an
+    /// operation is in flight.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.inner.as_mut()
+    }
+
+    /// Returns the buffered data that has not been read yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.read_buf[self.pos..self.filled]
+    }
+
+    /// Unwraps this `FromEmbeddedAsync`, returning the underlying object, or
+    /// `None` if an operation is in flight.
+    ///
+    /// Buffered data that has not been read or written yet is lost.
+    pub fn into_inner(self) -> Option<T> {
+        self.inner
+    }
+
+    fn start(&mut self, kind: OpKind, op: impl FnOnce(T) -> Op<T>) {
+        let inner = self.inner.take().expect("no operation in flight");
+        self.op = Some((kind, op(inner)));
+    }
+
+    /// Drives the operation in flight, if any, to completion.
+    fn poll_op(&mut self, cx: &mut Context<'_>) -> Poll<Result> {
+        let Some((kind, op)) = &mut self.op else {
+            return Poll::Ready(Ok(()));
+        };
+        let (inner, buf, res) = match op.as_mut().poll(cx) {
+            Poll::Ready(out) => out,
+            Poll::Pending => return Poll::Pending,
+        };
+        match kind {
+            OpKind::Read => {
+                self.read_buf = buf;
+                self.pos = 0;
+                self.filled = *res.as_ref().unwrap_or(&0);
+            }
+            OpKind::Write => self.write_buf = buf,
+            OpKind::Flush => {}
+        }
+        self.inner = Some(inner);
+        self.op = None;
+        Poll::Ready(res.map(drop))
+    }
+
+    fn op_kind(&self) -> Option<OpKind> {
+        self.op.as_ref().map(|(kind, _)| *kind)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug> fmt::Debug for FromEmbeddedAsync<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FromEmbeddedAsync")
+            .field("inner", &self.inner)
+            .field("op", &self.op_kind())
+            .field("buffer", &self.buffer())
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: eio_async::Read + 'static> AsyncRead for FromEmbeddedAsync<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        let nread = match self.as_mut().poll_fill_buf(cx) {
+            Poll::Ready(Ok(mut rem)) => crate::Read::read(&mut rem, buf)?,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        };
+        self.consume(nread);
+        Poll::Ready(Ok(nread))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: eio_async::Read + 'static> AsyncBufRead for FromEmbeddedAsync<T> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<&[u8]>> {
+        let this = self.get_mut();
+        while this.pos >= this.filled {
+            let reading = this.op_kind() == Some(OpKind::Read);
+            match this.poll_op(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+            if reading {
+                // Either data has arrived, or this is EOF.
+                break;
+            }
+            let mut buf = mem::take(&mut this.read_buf);
+            this.start(OpKind::Read, |mut inner| {
+                Box::pin(async move {
+                    let res = inner.read(&mut buf).await.map_err(from_embedded_error);
+                    (inner, buf, res)
+                })
+            });
+        }
+        Poll::Ready(Ok(this.buffer()))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.pos = core::cmp::min(this.pos + amt, this.filled);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: eio_async::Write + 'static> AsyncWrite for FromEmbeddedAsync<T> {
+    /// Copies as much of `buf` as fits into the write buffer, once the
+    /// operation in flight has completed.
+    ///
+    /// The data is then written out in the background of the following calls;
+    /// if that fails, the error is returned by the next call on this object.
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+        match this.poll_op(cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let len = buf.len().min(this.write_buf.len());
+        let mut data = mem::take(&mut this.write_buf);
+        data[..len].copy_from_slice(&buf[..len]);
+        this.start(OpKind::Write, |mut inner| {
+            Box::pin(async move {
+                let mut rest = &data[..len];
+                let mut res = Ok(len);
+                while !rest.is_empty() {
+                    match inner.write(rest).await {
+                        Ok(0) => {
+                            res = Err(axerrno::ax_err_type!(
+                                WriteZero,
+                                "failed to write whole buffer"
+                            ));
+                            break;
+                        }
+                        Ok(n) => rest = &rest[n..],
+                        Err(e) => {
+                            res = Err(from_embedded_error(e));
+                            break;
+                        }
+                    }
+                }
+                (inner, data, res)
+            })
+        });
+
+        // Get the write going right away, so that its waker is registered.
+        match this.poll_op(cx) {
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            _ => Poll::Ready(Ok(len)),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result> {
+        let this = self.get_mut();
+        loop {
+            let flushing = this.op_kind() == Some(OpKind::Flush);
+            match this.poll_op(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+            if flushing {
+                return Poll::Ready(Ok(()));
+            }
+            this.start(OpKind::Flush, |mut inner| {
+                Box::pin(async move {
+                    let res = inner.flush().await.map(|()| 0);
+                    (inner, Box::default(), res.map_err(from_embedded_error))
+                })
+            });
+        }
+    }
+}
+
+/// An adapter that implements the [`embedded_io`] traits for an `axio`
+/// object, and the [`embedded_io_async`] traits for an `axio` async object.
+///
+/// Errors are wrapped in [`EmbeddedError`]. Since [`embedded_io`] has no
+/// notion of non-blocking I/O, [`Error::WouldBlock`] is passed through as is
+/// and reported as [`ErrorKind::Other`].
+#[derive(Debug, Default, Clone)]
+pub struct ToEmbedded<T> {
+    inner: T,
+}
+
+impl<T> ToEmbedded<T> {
+    /// Wraps an `axio` object.
+    pub const fn new(inner: T) -> ToEmbedded<T> {
+        ToEmbedded { inner }
+    }
+
+    /// Gets a reference to the underlying object.
+    pub const fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying object.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps this `ToEmbedded`, returning the underlying object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> eio::ErrorType for ToEmbedded<T> {
+    type Error = EmbeddedError;
+}
+
+impl<T: Read> eio::Read for ToEmbedded<T> {
+    fn read(&mut self, buf: &mut [u8]) -> core::result::Result<usize, EmbeddedError> {
+        Ok(self.inner.read(buf)?)
+    }
+}
+
+impl<T: Write> eio::Write for ToEmbedded<T> {
+    fn write(&mut self, buf: &[u8]) -> core::result::Result<usize, EmbeddedError> {
+        Ok(self.inner.write(buf)?)
+    }
+
+    fn flush(&mut self) -> core::result::Result<(), EmbeddedError> {
+        Ok(self.inner.flush()?)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> core::result::Result<(), EmbeddedError> {
+        Ok(self.inner.write_all(buf)?)
+    }
+}
+
+impl<T: Seek> eio::Seek for ToEmbedded<T> {
+    fn seek(&mut self, pos: eio::SeekFrom) -> core::result::Result<u64, EmbeddedError> {
+        Ok(self.inner.seek(pos.into())?)
+    }
+
+    fn stream_position(&mut self) -> core::result::Result<u64, EmbeddedError> {
+        Ok(self.inner.stream_position()?)
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> core::result::Result<(), EmbeddedError> {
+        Ok(self.inner.seek_relative(offset)?)
+    }
+}
+
+impl<T: BufRead> eio::BufRead for ToEmbedded<T> {
+    fn fill_buf(&mut self) -> core::result::Result<&[u8], EmbeddedError> {
+        Ok(self.inner.fill_buf()?)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl<T: AsyncRead + Unpin> eio_async::Read for ToEmbedded<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> core::result::Result<usize, EmbeddedError> {
+        Ok(AsyncReadExt::read(&mut self.inner, buf).await?)
+    }
+}
+
+impl<T: AsyncWrite + Unpin> eio_async::Write for ToEmbedded<T> {
+    async fn write(&mut self, buf: &[u8]) -> core::result::Result<usize, EmbeddedError> {
+        Ok(AsyncWriteExt::write(&mut self.inner, buf).await?)
+    }
+
+    async fn flush(&mut self) -> core::result::Result<(), EmbeddedError> {
+        Ok(AsyncWriteExt::flush(&mut self.inner).await?)
+    }
+
+    async fn write_all(&mut self, buf: &[u8]) -> core::result::Result<(), EmbeddedError> {
+        Ok(AsyncWriteExt::write_all(&mut self.inner, buf).await?)
+    }
+}
+
+impl<T: AsyncSeek + Unpin> eio_async::Seek for ToEmbedded<T> {
+    async fn seek(&mut self, pos: eio::SeekFrom) -> core::result::Result<u64, EmbeddedError> {
+        Ok(AsyncSeekExt::seek(&mut self.inner, pos.into()).await?)
+    }
+}
+
+impl<T: AsyncBufRead + Unpin> eio_async::BufRead for ToEmbedded<T> {
+    async fn fill_buf(&mut self) -> core::result::Result<&[u8], EmbeddedError> {
+        Ok(FillBuf {
+            reader: Some(&mut self.inner),
+        }
+        .await?)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        Pin::new(&mut self.inner).consume(amt)
+    }
+}
+
+/// Future for [`AsyncBufRead::poll_fill_buf`].
+struct FillBuf<'a, R: ?Sized> {
+    reader: Option<&'a mut R>,
+}
+
+impl<'a, R: AsyncBufRead + Unpin + ?Sized> Future for FillBuf<'a, R> {
+    type Output = Result<&'a [u8]>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Some(reader) = self.reader.take() else {
+            return Poll::Ready(ax_err!(BadState, "`FillBuf` polled after completion"));
+        };
+        match Pin::new(&mut *reader).poll_fill_buf(cx) {
+            Poll::Ready(Ok(buf)) => {
+                // SAFETY: `reader` lives for `'a` and is not used again, so the
+                // buffer it returned lives for `'a` as well. The borrow checker
+                // cannot see this because `reader` is put back when pending.
+                Poll::Ready(Ok(unsafe { &*(buf as *const [u8]) }))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => {
+                self.reader = Some(reader);
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{
+    leb128::{self, Sleb128, Uleb128, Zigzag},
+    BufRead, Read, Result, Write,
+};
+
+macro_rules! read_impls {
+    ($($ty:ident: $le:ident, $be:ident;)*) => {$(
+        #[doc = concat!("Reads a little-endian `", stringify!($ty), "`.")]
+        #[inline]
+        fn $le(&mut self) -> Result<$ty> {
+            let mut buf = [0; size_of::<$ty>()];
+            self.read_exact(&mut buf)?;
+            Ok($ty::from_le_bytes(buf))
+        }
+
+        #[doc = concat!("Reads a big-endian `", stringify!($ty), "`.")]
+        #[inline]
+        fn $be(&mut self) -> Result<$ty> {
+            let mut buf = [0; size_of::<$ty>()];
+            self.read_exact(&mut buf)?;
+            Ok($ty::from_be_bytes(buf))
+        }
+    )*};
+}
+
+macro_rules! write_impls {
+    ($($ty:ident: $le:ident, $be:ident;)*) => {$(
+        #[doc = concat!("Writes a little-endian `", stringify!($ty), "`.")]
+        #[inline]
+        fn $le(&mut self, n: $ty) -> Result {
+            self.write_all(&n.to_le_bytes())
+        }
+
+        #[doc = concat!("Writes a big-endian `", stringify!($ty), "`.")]
+        #[inline]
+        fn $be(&mut self, n: $ty) -> Result {
+            self.write_all(&n.to_be_bytes())
+        }
+    )*};
+}
+
+/// Extends [`Read`] with methods for reading numbers in a given byte order,
+/// or as variable-length integers.
+///
+/// Fixed-size methods read the exact size of the number with [`read_exact`],
+/// so they fail with an [`Io`] error if EOF is reached first. Over a
+/// [`BufReader`], values are copied straight out of the buffer.
+///
+/// [`read_exact`]: Read::read_exact
+/// [`Io`]: crate::Error::Io
+/// [`BufReader`]: crate::BufReader
+pub trait ReadBytesExt: Read {
+    /// Reads an unsigned 8 bit integer.
+    #[inline]
+    fn read_u8(&mut self) -> Result<u8> {
+        let mut buf = [0; 1];
+        self.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    /// Reads a signed 8 bit integer.
+    #[inline]
+    fn read_i8(&mut self) -> Result<i8> {
+        self.read_u8().map(|n| n as i8)
+    }
+
+    read_impls! {
+        u16: read_u16_le, read_u16_be;
+        u32: read_u32_le, read_u32_be;
+        u64: read_u64_le, read_u64_be;
+        u128: read_u128_le, read_u128_be;
+        i16: read_i16_le, read_i16_be;
+        i32: read_i32_le, read_i32_be;
+        i64: read_i64_le, read_i64_be;
+        i128: read_i128_le, read_i128_be;
+        f32: read_f32_le, read_f32_be;
+        f64: read_f64_le, read_f64_be;
+    }
+
+    /// Reads an unsigned LEB128 value.
+    ///
+    /// Fails with [`InvalidData`] if the value does not fit in a `u64`.
+    ///
+    /// The value is read one byte at a time; over a [`BufRead`] prefer
+    /// [`BufReadBytesExt::read_uleb128_buffered`].
+    ///
+    /// [`InvalidData`]: crate::Error::InvalidData
+    fn read_uleb128(&mut self) -> Result<u64> {
+        leb128::read::<Uleb128, _>(self)
+    }
+
+    /// Reads a signed LEB128 value.
+    ///
+    /// Fails with [`InvalidData`] if the value does not fit in an `i64`.
+    ///
+    /// The value is read one byte at a time; over a [`BufRead`] prefer
+    /// [`BufReadBytesExt::read_sleb128_buffered`].
+    ///
+    /// [`InvalidData`]: crate::Error::InvalidData
+    fn read_sleb128(&mut self) -> Result<i64> {
+        leb128::read::<Sleb128, _>(self)
+    }
+
+    /// Reads a zigzag-encoded signed varint, as used by protobuf's `sint64`.
+    ///
+    /// Fails with [`InvalidData`] if the value does not fit in an `i64`.
+    ///
+    /// The value is read one byte at a time; over a [`BufRead`] prefer
+    /// [`BufReadBytesExt::read_zigzag_buffered`].
+    ///
+    /// [`InvalidData`]: crate::Error::InvalidData
+    fn read_zigzag(&mut self) -> Result<i64> {
+        leb128::read::<Zigzag, _>(self)
+    }
+}
+
+impl<R: Read + ?Sized> ReadBytesExt for R {}
+
+/// Extends [`BufRead`] with varint decoders that work directly on the
+/// internal buffer.
+///
+/// These behave exactly like their [`ReadBytesExt`] counterparts, but scan
+/// [`fill_buf`] instead of issuing one read per byte.
+///
+/// [`fill_buf`]: BufRead::fill_buf
+pub trait BufReadBytesExt: BufRead {
+    /// Reads an unsigned LEB128 value. See [`ReadBytesExt::read_uleb128`].
+    fn read_uleb128_buffered(&mut self) -> Result<u64> {
+        leb128::read_buffered::<Uleb128, _>(self)
+    }
+
+    /// Reads a signed LEB128 value. See [`ReadBytesExt::read_sleb128`].
+    fn read_sleb128_buffered(&mut self) -> Result<i64> {
+        leb128::read_buffered::<Sleb128, _>(self)
+    }
+
+    /// Reads a zigzag-encoded signed varint. See [`ReadBytesExt::read_zigzag`].
+    fn read_zigzag_buffered(&mut self) -> Result<i64> {
+        leb128::read_buffered::<Zigzag, _>(self)
+    }
+}
+
+impl<R: BufRead + ?Sized> BufReadBytesExt for R {}
+
+/// Extends [`Write`] with methods for writing numbers in a given byte order,
+/// or as variable-length integers.
+///
+/// Every method writes the whole encoded number with [`write_all`].
+///
+/// [`write_all`]: Write::write_all
+pub trait WriteBytesExt: Write {
+    /// Writes an unsigned 8 bit integer.
+    #[inline]
+    fn write_u8(&mut self, n: u8) -> Result {
+        self.write_all(&[n])
+    }
+
+    /// Writes a signed 8 bit integer.
+    #[inline]
+    fn write_i8(&mut self, n: i8) -> Result {
+        self.write_all(&[n as u8])
+    }
+
+    write_impls! {
+        u16: write_u16_le, write_u16_be;
+        u32: write_u32_le, write_u32_be;
+        u64: write_u64_le, write_u64_be;
+        u128: write_u128_le, write_u128_be;
+        i16: write_i16_le, write_i16_be;
+        i32: write_i32_le, write_i32_be;
+        i64: write_i64_le, write_i64_be;
+        i128: write_i128_le, write_i128_be;
+        f32: write_f32_le, write_f32_be;
+        f64: write_f64_le, write_f64_be;
+    }
+
+    /// Writes an unsigned LEB128 value.
+    fn write_uleb128(&mut self, n: u64) -> Result {
+        leb128::write_uleb128(self, n)
+    }
+
+    /// Writes a signed LEB128 value.
+    fn write_sleb128(&mut self, n: i64) -> Result {
+        leb128::write_sleb128(self, n)
+    }
+
+    /// Writes a zigzag-encoded signed varint, as used by protobuf's `sint64`.
+    fn write_zigzag(&mut self, n: i64) -> Result {
+        leb128::write_zigzag(self, n)
+    }
+}
+
+impl<W: Write + ?Sized> WriteBytesExt for W {}
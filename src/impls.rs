@@ -1,12 +1,344 @@
-use core::{cmp, mem};
+use core::{cmp, fmt, mem};
 
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, collections::VecDeque, string::String, vec::Vec};
 use axerrno::ax_bail;
 
+#[cfg(feature = "alloc")]
+use crate::DEFAULT_BUF_SIZE;
 use crate::{
     buf::{Buf, BufMut},
-    Read, Result, Write,
+    BorrowedCursor, BufRead, IoSlice, IoSliceMut, Read, Result, Seek, SeekFrom, Write,
 };
 
+impl<R: Read + ?Sized> Read for &mut R {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        (**self).read(buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        (**self).is_read_vectored()
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_to_end(buf)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_to_string(buf)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        (**self).read_exact(buf)
+    }
+
+    #[inline]
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        (**self).read_buf(cursor)
+    }
+
+    #[inline]
+    fn read_buf_exact(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        (**self).read_buf_exact(cursor)
+    }
+}
+
+impl<W: Write + ?Sized> Write for &mut W {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (**self).write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        (**self).is_write_vectored()
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        (**self).write_all(buf)
+    }
+
+    #[inline]
+    fn write_all_vectored(&mut self, bufs: &mut [IoSlice<'_>]) -> Result<()> {
+        (**self).write_all_vectored(bufs)
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
+        (**self).write_fmt(fmt)
+    }
+}
+
+impl<S: Seek + ?Sized> Seek for &mut S {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        (**self).seek(pos)
+    }
+
+    #[inline]
+    fn rewind(&mut self) -> Result<()> {
+        (**self).rewind()
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> Result<u64> {
+        (**self).stream_position()
+    }
+
+    #[inline]
+    fn stream_len(&mut self) -> Result<u64> {
+        (**self).stream_len()
+    }
+
+    #[inline]
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        (**self).seek_relative(offset)
+    }
+}
+
+impl<B: BufRead + ?Sized> BufRead for &mut B {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        (**self).fill_buf()
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt)
+    }
+
+    #[inline]
+    fn has_data_left(&mut self) -> Result<bool> {
+        (**self).has_data_left()
+    }
+
+    #[inline]
+    fn skip_until(&mut self, byte: u8) -> Result<usize> {
+        (**self).skip_until(byte)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until(byte, buf)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_line(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_line(buf)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Read + ?Sized> Read for Box<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        (**self).read(buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        (**self).is_read_vectored()
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_to_end(buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_to_string(buf)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        (**self).read_exact(buf)
+    }
+
+    #[inline]
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        (**self).read_buf(cursor)
+    }
+
+    #[inline]
+    fn read_buf_exact(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        (**self).read_buf_exact(cursor)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<W: Write + ?Sized> Write for Box<W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (**self).write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        (**self).is_write_vectored()
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        (**self).write_all(buf)
+    }
+
+    #[inline]
+    fn write_all_vectored(&mut self, bufs: &mut [IoSlice<'_>]) -> Result<()> {
+        (**self).write_all_vectored(bufs)
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
+        (**self).write_fmt(fmt)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Seek + ?Sized> Seek for Box<S> {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        (**self).seek(pos)
+    }
+
+    #[inline]
+    fn rewind(&mut self) -> Result<()> {
+        (**self).rewind()
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> Result<u64> {
+        (**self).stream_position()
+    }
+
+    #[inline]
+    fn stream_len(&mut self) -> Result<u64> {
+        (**self).stream_len()
+    }
+
+    #[inline]
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        (**self).seek_relative(offset)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<B: BufRead + ?Sized> BufRead for Box<B> {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        (**self).fill_buf()
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt)
+    }
+
+    #[inline]
+    fn has_data_left(&mut self) -> Result<bool> {
+        (**self).has_data_left()
+    }
+
+    #[inline]
+    fn skip_until(&mut self, byte: u8) -> Result<usize> {
+        (**self).skip_until(byte)
+    }
+
+    #[inline]
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until(byte, buf)
+    }
+
+    #[inline]
+    fn read_line(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_line(buf)
+    }
+}
+
+impl<B: Buf + ?Sized> Buf for &mut B {
+    #[inline]
+    fn remaining(&self) -> usize {
+        (**self).remaining()
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        (**self).chunk()
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        (**self).advance(cnt)
+    }
+
+    #[inline]
+    fn has_remaining(&self) -> bool {
+        (**self).has_remaining()
+    }
+}
+
+impl<B: BufMut + ?Sized> BufMut for &mut B {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        (**self).remaining_mut()
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut [u8] {
+        (**self).chunk_mut()
+    }
+
+    #[inline]
+    fn advance_mut(&mut self, cnt: usize) {
+        (**self).advance_mut(cnt)
+    }
+
+    #[inline]
+    fn has_remaining_mut(&self) -> bool {
+        (**self).has_remaining_mut()
+    }
+}
+
 impl Read for &[u8] {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
@@ -26,6 +358,24 @@ impl Read for &[u8] {
         Ok(amt)
     }
 
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let mut nread = 0;
+        for buf in bufs {
+            nread += self.read(buf)?;
+            if self.is_empty() {
+                break;
+            }
+        }
+
+        Ok(nread)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
         if buf.len() > self.len() {
@@ -47,9 +397,36 @@ impl Read for &[u8] {
         Ok(())
     }
 
+    #[inline]
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let amt = cmp::min(cursor.capacity(), self.len());
+        let (a, b) = self.split_at(amt);
+
+        cursor.append(a);
+
+        *self = b;
+        Ok(())
+    }
+
+    #[inline]
+    fn read_buf_exact(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        if cursor.capacity() > self.len() {
+            // Append everything we can to the cursor.
+            cursor.append(self);
+            *self = &self[self.len()..];
+            ax_bail!(Io, "failed to fill whole buffer");
+        }
+        let (a, b) = self.split_at(cursor.capacity());
+
+        cursor.append(a);
+
+        *self = b;
+        Ok(())
+    }
+
     #[inline]
     #[cfg(feature = "alloc")]
-    fn read_to_end(&mut self, buf: &mut alloc::vec::Vec<u8>) -> Result<usize> {
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
         buf.extend_from_slice(self);
         let len = self.len();
         *self = &self[len..];
@@ -57,6 +434,18 @@ impl Read for &[u8] {
     }
 }
 
+impl BufRead for &[u8] {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(*self)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        *self = &self[amt..];
+    }
+}
+
 impl Write for &mut [u8] {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
@@ -76,6 +465,191 @@ impl Write for &mut [u8] {
         Ok(amt)
     }
 
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let mut nwritten = 0;
+        for buf in bufs {
+            nwritten += self.write(buf)?;
+            if self.is_empty() {
+                break;
+            }
+        }
+
+        Ok(nwritten)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Write is implemented for `Vec<u8>` by appending to the vector.
+/// The vector will grow as needed.
+#[cfg(feature = "alloc")]
+impl Write for Vec<u8> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let len = bufs.iter().map(|b| b.len()).sum();
+        self.try_reserve(len)
+            .map_err(|e| axerrno::ax_err_type!(NoMemory, e))?;
+        for buf in bufs {
+            self.extend_from_slice(buf);
+        }
+        Ok(len)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.try_reserve(buf.len())
+            .map_err(|e| axerrno::ax_err_type!(NoMemory, e))?;
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Read is implemented for `VecDeque<u8>` by consuming bytes from the front
+/// of the `VecDeque`.
+#[cfg(feature = "alloc")]
+impl Read for VecDeque<u8> {
+    /// Fill `buf` with the contents of the "front" slice as returned by
+    /// [`as_slices`][`VecDeque::as_slices`]. If the contained byte slices of
+    /// the `VecDeque` are discontiguous, multiple calls to `read` will be
+    /// needed to read the entire content.
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let (ref mut front, _) = self.as_slices();
+        let n = Read::read(front, buf)?;
+        self.drain(..n);
+        Ok(n)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let (ref mut front, _) = self.as_slices();
+        let n = Read::read_vectored(front, bufs)?;
+        self.drain(..n);
+        Ok(n)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        if buf.len() > self.len() {
+            ax_bail!(Io, "failed to fill whole buffer");
+        }
+        let (front, back) = self.as_slices();
+        if buf.len() <= front.len() {
+            buf.copy_from_slice(&front[..buf.len()]);
+        } else {
+            let (buf_front, buf_back) = buf.split_at_mut(front.len());
+            buf_front.copy_from_slice(front);
+            buf_back.copy_from_slice(&back[..buf_back.len()]);
+        }
+        self.drain(..buf.len());
+        Ok(())
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        // The total len is known upfront so we can reserve it in a single call.
+        let len = self.len();
+        buf.try_reserve(len)
+            .map_err(|e| axerrno::ax_err_type!(NoMemory, e))?;
+
+        let (front, back) = self.as_slices();
+        buf.extend_from_slice(front);
+        buf.extend_from_slice(back);
+        self.clear();
+        Ok(len)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        // SAFETY: We only append to the buffer
+        unsafe { crate::append_to_string(buf, |buf| self.read_to_end(buf)) }
+    }
+}
+
+/// BufRead is implemented for `VecDeque<u8>` by reading bytes from the front
+/// of the `VecDeque`.
+#[cfg(feature = "alloc")]
+impl BufRead for VecDeque<u8> {
+    /// Returns the contents of the "front" slice as returned by
+    /// [`as_slices`][`VecDeque::as_slices`]. If the contained byte slices of
+    /// the `VecDeque` are discontiguous, multiple calls to `fill_buf` will be
+    /// needed to read the entire content.
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        let (front, _) = self.as_slices();
+        Ok(front)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.drain(..amt);
+    }
+}
+
+/// Write is implemented for `VecDeque<u8>` by appending to the `VecDeque`,
+/// growing it as needed.
+#[cfg(feature = "alloc")]
+impl Write for VecDeque<u8> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let len = bufs.iter().map(|b| b.len()).sum();
+        self.try_reserve(len)
+            .map_err(|e| axerrno::ax_err_type!(NoMemory, e))?;
+        for buf in bufs {
+            self.extend(&**buf);
+        }
+        Ok(len)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.try_reserve(buf.len())
+            .map_err(|e| axerrno::ax_err_type!(NoMemory, e))?;
+        self.extend(buf);
+        Ok(())
+    }
+
     #[inline]
     fn flush(&mut self) -> Result<()> {
         Ok(())
@@ -83,25 +657,100 @@ impl Write for &mut [u8] {
 }
 
 impl Buf for &[u8] {
+    #[inline]
     fn remaining(&self) -> usize {
         self.len()
     }
 
-    fn consume(&mut self, mut f: impl FnMut(&[u8]) -> Result<usize>) -> Result<usize> {
-        let consumed = f(self)?;
-        *self = &self[consumed..];
-        Ok(consumed)
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        *self = &self[cnt..];
     }
 }
 
 impl BufMut for &mut [u8] {
+    #[inline]
     fn remaining_mut(&self) -> usize {
         self.len()
     }
 
-    fn fill(&mut self, mut f: impl FnMut(&mut [u8]) -> Result<usize>) -> Result<usize> {
-        let filled = f(self)?;
-        *self = mem::take(self).split_at_mut(filled).1;
-        Ok(filled)
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut [u8] {
+        self
+    }
+
+    #[inline]
+    fn advance_mut(&mut self, cnt: usize) {
+        *self = &mut mem::take(self)[cnt..];
+    }
+}
+
+/// Buf is implemented for `VecDeque<u8>` by consuming bytes from the front of
+/// the `VecDeque`.
+#[cfg(feature = "alloc")]
+impl Buf for VecDeque<u8> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns the "front" slice as returned by
+    /// [`as_slices`][`VecDeque::as_slices`].
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        let (front, _) = self.as_slices();
+        front
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        self.drain(..cnt);
+    }
+}
+
+/// BufMut is implemented for `VecDeque<u8>` by appending bytes to the back of
+/// the `VecDeque`, which grows as needed.
+///
+/// A `VecDeque` cannot lend out its spare capacity uninitialized, so
+/// [`chunk_mut`] appends a zero-filled chunk to the back and
+/// [`advance_mut`] drops the part of it that was not filled. Each call to
+/// `chunk_mut` must therefore be followed by exactly one call to
+/// `advance_mut`, as [`put_with`], [`transfer`] and [`read_into_buf`] do.
+///
+/// [`chunk_mut`]: BufMut::chunk_mut
+/// [`advance_mut`]: BufMut::advance_mut
+/// [`put_with`]: BufMut::put_with
+/// [`transfer`]: crate::transfer
+/// [`read_into_buf`]: crate::read_into_buf
+#[cfg(feature = "alloc")]
+impl BufMut for VecDeque<u8> {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        isize::MAX as usize - self.len()
+    }
+
+    /// Appends `DEFAULT_BUF_SIZE` zero bytes and returns the first contiguous
+    /// run of them.
+    fn chunk_mut(&mut self) -> &mut [u8] {
+        let len = self.len();
+        self.resize(len + DEFAULT_BUF_SIZE, 0);
+        let (front, back) = self.as_mut_slices();
+        if len < front.len() {
+            &mut front[len..]
+        } else {
+            &mut back[len - front.len()..]
+        }
+    }
+
+    /// Keeps the first `cnt` bytes of the chunk appended by the last
+    /// [`chunk_mut`](BufMut::chunk_mut) and drops the rest.
+    fn advance_mut(&mut self, cnt: usize) {
+        assert!(cnt <= DEFAULT_BUF_SIZE, "advanced past the chunk");
+        self.truncate(self.len().saturating_sub(DEFAULT_BUF_SIZE - cnt));
     }
 }
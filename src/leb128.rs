@@ -0,0 +1,159 @@
+use axerrno::ax_bail;
+
+use crate::{BufRead, Read, Result, Write};
+
+/// Incremental decoder of a single varint, fed one byte at a time.
+pub(crate) trait Decoder: Default {
+    type Output;
+
+    /// Feeds the next byte, returning the value once it is complete.
+    fn feed(&mut self, byte: u8) -> Result<Option<Self::Output>>;
+}
+
+/// Decoder of an unsigned LEB128 value.
+#[derive(Default)]
+pub(crate) struct Uleb128 {
+    value: u64,
+    shift: u32,
+}
+
+impl Decoder for Uleb128 {
+    type Output = u64;
+
+    fn feed(&mut self, byte: u8) -> Result<Option<u64>> {
+        let payload = u64::from(byte & 0x7f);
+        // The 10th byte only has room for the topmost bit of a `u64`.
+        if self.shift == 63 && (payload > 1 || byte & 0x80 != 0) {
+            ax_bail!(InvalidData, "LEB128 value overflows u64");
+        }
+        self.value |= payload << self.shift;
+        if byte & 0x80 == 0 {
+            return Ok(Some(self.value));
+        }
+        self.shift += 7;
+        Ok(None)
+    }
+}
+
+/// Decoder of a signed LEB128 value.
+#[derive(Default)]
+pub(crate) struct Sleb128 {
+    value: i64,
+    shift: u32,
+}
+
+impl Decoder for Sleb128 {
+    type Output = i64;
+
+    fn feed(&mut self, byte: u8) -> Result<Option<i64>> {
+        let payload = byte & 0x7f;
+        // The 10th byte only has room for the sign bit of an `i64`, so it
+        // must be a plain sign extension.
+        if self.shift == 63 && (byte & 0x80 != 0 || (payload != 0 && payload != 0x7f)) {
+            ax_bail!(InvalidData, "LEB128 value overflows i64");
+        }
+        self.value |= i64::from(payload) << self.shift;
+        self.shift += 7;
+        if byte & 0x80 == 0 {
+            if self.shift < 64 && payload & 0x40 != 0 {
+                self.value |= -1 << self.shift;
+            }
+            return Ok(Some(self.value));
+        }
+        Ok(None)
+    }
+}
+
+/// Decoder of a zigzag-encoded signed varint.
+#[derive(Default)]
+pub(crate) struct Zigzag(Uleb128);
+
+impl Decoder for Zigzag {
+    type Output = i64;
+
+    fn feed(&mut self, byte: u8) -> Result<Option<i64>> {
+        Ok(self
+            .0
+            .feed(byte)?
+            .map(|n| (n >> 1) as i64 ^ -((n & 1) as i64)))
+    }
+}
+
+/// Decodes a varint from `reader` one byte at a time.
+pub(crate) fn read<D: Decoder, R: Read + ?Sized>(reader: &mut R) -> Result<D::Output> {
+    let mut decoder = D::default();
+    loop {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        if let Some(value) = decoder.feed(byte[0])? {
+            return Ok(value);
+        }
+    }
+}
+
+/// Decodes a varint straight out of the buffer of `reader`.
+///
+/// Like [`read`], the bytes of the value are consumed up to and including the
+/// last one, or the one that caused an error.
+pub(crate) fn read_buffered<D: Decoder, R: BufRead + ?Sized>(reader: &mut R) -> Result<D::Output> {
+    let mut decoder = D::default();
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            ax_bail!(Io, "failed to fill whole buffer");
+        }
+        for (i, &byte) in buf.iter().enumerate() {
+            match decoder.feed(byte) {
+                Ok(None) => {}
+                result => {
+                    reader.consume(i + 1);
+                    return result.map(|value| value.unwrap());
+                }
+            }
+        }
+        let len = buf.len();
+        reader.consume(len);
+    }
+}
+
+/// Encodes `value` as unsigned LEB128 into `writer`.
+pub(crate) fn write_uleb128<W: Write + ?Sized>(writer: &mut W, mut value: u64) -> Result {
+    let mut buf = [0; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&buf[..len])
+}
+
+/// Encodes `value` as signed LEB128 into `writer`.
+pub(crate) fn write_sleb128<W: Write + ?Sized>(writer: &mut W, mut value: i64) -> Result {
+    let mut buf = [0; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if done {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&buf[..len])
+}
+
+/// Encodes `value` as a zigzag varint into `writer`.
+pub(crate) fn write_zigzag<W: Write + ?Sized>(writer: &mut W, value: i64) -> Result {
+    write_uleb128(writer, ((value << 1) ^ (value >> 63)) as u64)
+}
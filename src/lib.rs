@@ -7,19 +7,72 @@
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 use core::fmt;
 
+mod adapters;
+pub mod async_io;
 mod buf;
 mod buffered;
+mod checksum;
+mod copy;
+mod cursor;
+#[cfg(feature = "embedded-io")]
+mod embedded;
+mod endian;
 mod error;
 mod impls;
+mod iovec;
+mod leb128;
+#[cfg(feature = "alloc")]
+mod pipe;
+mod poll;
+mod positioned;
 pub mod prelude;
+#[cfg(feature = "std")]
+mod std_compat;
+mod util;
+
+pub use core::io::{BorrowedBuf, BorrowedCursor};
 
 pub use self::{
-    buf::{Buf, BufMut},
+    adapters::{Bytes, Chain, Take},
+    async_io::{AsyncBufRead, AsyncBufReader, AsyncRead, AsyncSeek, AsyncWrite},
+    buf::{read_into_buf, transfer, write_from_buf, Buf, BufMut},
     buffered::BufReader,
+    checksum::{Adler32, Checksum, ChecksumReader, ChecksumWriter, Crc32, Crc32c},
+    copy::{copy, copy_buf, copy_with_buf},
+    cursor::Cursor,
+    endian::{BufReadBytesExt, ReadBytesExt, WriteBytesExt},
     error::{Error, Result},
+    iovec::{IoSlice, IoSliceMut},
+    poll::{IoEvents, Pollable},
+    positioned::{ReadAt, ReadAtCursor, WriteAt},
+    util::{empty, repeat, sink, Empty, Repeat, Sink},
+};
+
+#[allow(deprecated)]
+pub use self::poll::PollState;
+
+#[cfg(feature = "alloc")]
+pub use self::{
+    adapters::{Lines, Split},
+    buffered::{BufWriter, IntoInnerError, LineWriter},
+    pipe::{pipe, pipe_with_pipe_buf, PipeReader, PipeWriter, DEFAULT_PIPE_BUF},
+};
+
+#[cfg(all(feature = "embedded-io", feature = "alloc"))]
+pub use self::embedded::FromEmbeddedAsync;
+#[cfg(feature = "embedded-io")]
+pub use self::embedded::{
+    from_embedded_error_kind, to_embedded_error_kind, EmbeddedError, FromEmbedded, ToEmbedded,
+};
+
+#[cfg(feature = "std")]
+pub use self::std_compat::{
+    from_std_error, from_std_error_kind, to_std_error, to_std_error_kind, FromStd, ToStd,
 };
 
 #[cfg(feature = "alloc")]
@@ -39,8 +92,6 @@ pub fn default_read_to_end<R: Read + ?Sized>(
     buf: &mut Vec<u8>,
     size_hint: Option<usize>,
 ) -> Result<usize> {
-    use core::io::BorrowedBuf;
-
     let start_len = buf.len();
     let start_cap = buf.capacity();
     // Optionally limit the maximum bytes read on each iteration.
@@ -104,9 +155,7 @@ pub fn default_read_to_end<R: Read + ?Sized>(
         }
 
         let mut cursor = read_buf.unfilled();
-        // Difference from `std`: We don't have a `read_buf` method that returns both data and an error, so we return early on error.
-        let n = r.read(cursor.ensure_init().init_mut())?;
-        cursor.advance(n);
+        let result = r.read_buf(cursor.reborrow());
 
         let unfilled_but_initialized = cursor.init_mut().len();
         let bytes_read = cursor.written();
@@ -118,6 +167,9 @@ pub fn default_read_to_end<R: Read + ?Sized>(
             buf.set_len(new_len);
         }
 
+        // Now that all data is pushed to the vector, we can fail without data loss
+        result?;
+
         if bytes_read == 0 {
             return Ok(buf.len() - start_len);
         }
@@ -152,12 +204,107 @@ pub fn default_read_to_end<R: Read + ?Sized>(
     }
 }
 
+/// Default [`Read::read_vectored`] implementation: reads into the first
+/// non-empty buffer.
+fn default_read_vectored<F>(read: F, bufs: &mut [IoSliceMut<'_>]) -> Result<usize>
+where
+    F: FnOnce(&mut [u8]) -> Result<usize>,
+{
+    let buf = bufs
+        .iter_mut()
+        .find(|b| !b.is_empty())
+        .map_or(&mut [][..], |b| &mut **b);
+    read(buf)
+}
+
+/// Default [`Write::write_vectored`] implementation: writes the first
+/// non-empty buffer.
+fn default_write_vectored<F>(write: F, bufs: &[IoSlice<'_>]) -> Result<usize>
+where
+    F: FnOnce(&[u8]) -> Result<usize>,
+{
+    let buf = bufs
+        .iter()
+        .find(|b| !b.is_empty())
+        .map_or(&[][..], |b| &**b);
+    write(buf)
+}
+
+/// Default [`Read::read_exact`] implementation: calls [`Read::read`] in a loop
+/// until `buf` is filled.
+fn default_read_exact<R: Read + ?Sized>(r: &mut R, mut buf: &mut [u8]) -> Result {
+    while !buf.is_empty() {
+        match r.read(buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                let tmp = buf;
+                buf = &mut tmp[n..];
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if !buf.is_empty() {
+        ax_bail!(Io, "failed to read whole buffer");
+    } else {
+        Ok(())
+    }
+}
+
+/// Default [`Read::read_buf`] implementation: initializes the unfilled part of
+/// `cursor` and calls `read` on it.
+fn default_read_buf<F>(read: F, mut cursor: BorrowedCursor<'_>) -> Result
+where
+    F: FnOnce(&mut [u8]) -> Result<usize>,
+{
+    let n = read(cursor.ensure_init().init_mut())?;
+    cursor.advance(n);
+    Ok(())
+}
+
+/// Default [`Read::read_buf_exact`] implementation: calls [`Read::read_buf`] in
+/// a loop until `cursor` is full.
+fn default_read_buf_exact<R: Read + ?Sized>(r: &mut R, mut cursor: BorrowedCursor<'_>) -> Result {
+    while cursor.capacity() > 0 {
+        let prev_written = cursor.written();
+        r.read_buf(cursor.reborrow())?;
+        if cursor.written() == prev_written {
+            ax_bail!(Io, "failed to read whole buffer");
+        }
+    }
+    Ok(())
+}
+
 /// The `Read` trait allows for reading bytes from a source.
 pub trait Read {
     /// Pull some bytes from this source into the specified buffer, returning
     /// how many bytes were read.
     fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
 
+    /// Like [`read`], except that it reads into a slice of buffers.
+    ///
+    /// Data is copied to fill each buffer in order, with the final buffer
+    /// written to possibly being only partially filled. This method must
+    /// behave equivalently to a single call to [`read`] with concatenated
+    /// buffers.
+    ///
+    /// The default implementation calls [`read`] with the first non-empty
+    /// buffer provided, or an empty one if none exists.
+    ///
+    /// [`read`]: Read::read
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        default_read_vectored(|b| self.read(b), bufs)
+    }
+
+    /// Determines if this `Read`er has an efficient [`read_vectored`]
+    /// implementation.
+    ///
+    /// The default implementation returns `false`.
+    ///
+    /// [`read_vectored`]: Read::read_vectored
+    fn is_read_vectored(&self) -> bool {
+        false
+    }
+
     /// Read all bytes until EOF in this source, placing them into `buf`.
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
@@ -171,22 +318,94 @@ pub trait Read {
     }
 
     /// Read the exact number of bytes required to fill `buf`.
-    fn read_exact(&mut self, mut buf: &mut [u8]) -> Result {
-        while !buf.is_empty() {
-            match self.read(buf) {
-                Ok(0) => break,
-                Ok(n) => {
-                    let tmp = buf;
-                    buf = &mut tmp[n..];
-                }
-                Err(e) => return Err(e),
-            }
-        }
-        if !buf.is_empty() {
-            ax_bail!(Io, "failed to read whole buffer");
-        } else {
-            Ok(())
-        }
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result {
+        default_read_exact(self, buf)
+    }
+
+    /// Pull some bytes from this source into the specified buffer.
+    ///
+    /// This is equivalent to the [`read`] method, except that it is passed a
+    /// [`BorrowedCursor`] rather than `[u8]` to allow use with uninitialized
+    /// buffers. The new data will be appended to any existing contents of
+    /// `cursor`.
+    ///
+    /// The default implementation delegates to [`read`], which requires the
+    /// unfilled part of the buffer to be zeroed first. Readers that can write
+    /// into uninitialized memory should override it.
+    ///
+    /// [`read`]: Read::read
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result {
+        default_read_buf(|b| self.read(b), cursor)
+    }
+
+    /// Reads the exact number of bytes required to fill `cursor`.
+    ///
+    /// This is similar to the [`read_exact`] method, except that it is passed
+    /// a [`BorrowedCursor`] rather than `[u8]` to allow use with uninitialized
+    /// buffers. If EOF is reached first, an [`Io`] error is returned and the
+    /// bytes read so far stay in `cursor`.
+    ///
+    /// [`read_exact`]: Read::read_exact
+    /// [`Io`]: Error::Io
+    fn read_buf_exact(&mut self, cursor: BorrowedCursor<'_>) -> Result {
+        default_read_buf_exact(self, cursor)
+    }
+
+    /// Creates a "by reference" adaptor for this instance of `Read`.
+    ///
+    /// The returned adapter also implements `Read` and will simply borrow this
+    /// current reader.
+    fn by_ref(&mut self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Transforms this `Read` instance to an [`Iterator`] over its bytes.
+    ///
+    /// The returned type implements [`Iterator`] where the [`Item`] is
+    /// <code>[Result]<[u8]></code>. The yielded item is [`Ok`] if a byte was
+    /// successfully read and [`Err`] otherwise. EOF is mapped to returning
+    /// [`None`] from this iterator.
+    ///
+    /// Each item issues one single-byte [`read`], so wrapping unbuffered
+    /// readers in a [`BufReader`] first is recommended.
+    ///
+    /// [`Item`]: Iterator::Item
+    /// [`read`]: Read::read
+    fn bytes(self) -> Bytes<Self>
+    where
+        Self: Sized,
+    {
+        Bytes::new(self)
+    }
+
+    /// Creates an adapter which will chain this stream with another.
+    ///
+    /// The returned `Read` instance will first read all bytes from this object
+    /// until EOF is encountered. Afterwards the output is equivalent to the
+    /// output of `next`.
+    fn chain<R: Read>(self, next: R) -> Chain<Self, R>
+    where
+        Self: Sized,
+    {
+        Chain::new(self, next)
+    }
+
+    /// Creates an adapter which will read at most `limit` bytes from it.
+    ///
+    /// This function returns a new instance of `Read` which will read at most
+    /// `limit` bytes, after which it will always return EOF (`Ok(0)`). Any
+    /// read errors will not count towards the number of bytes read and future
+    /// calls to [`read`] may succeed.
+    ///
+    /// [`read`]: Read::read
+    fn take(self, limit: u64) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take::new(self, limit)
     }
 }
 
@@ -195,6 +414,30 @@ pub trait Write {
     /// Write a buffer into this writer, returning how many bytes were written.
     fn write(&mut self, buf: &[u8]) -> Result<usize>;
 
+    /// Like [`write`], except that it writes from a slice of buffers.
+    ///
+    /// Data is copied from each buffer in order, with the final buffer read
+    /// from possibly being only partially consumed. This method must behave
+    /// as a call to [`write`] with the buffers concatenated would.
+    ///
+    /// The default implementation calls [`write`] with the first non-empty
+    /// buffer provided, or an empty one if none exists.
+    ///
+    /// [`write`]: Write::write
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        default_write_vectored(|b| self.write(b), bufs)
+    }
+
+    /// Determines if this `Write`r has an efficient [`write_vectored`]
+    /// implementation.
+    ///
+    /// The default implementation returns `false`.
+    ///
+    /// [`write_vectored`]: Write::write_vectored
+    fn is_write_vectored(&self) -> bool {
+        false
+    }
+
     /// Flush this output stream, ensuring that all intermediately buffered
     /// contents reach their destination.
     fn flush(&mut self) -> Result;
@@ -211,6 +454,29 @@ pub trait Write {
         Ok(())
     }
 
+    /// Attempts to write multiple buffers into this writer.
+    ///
+    /// This method will continuously call [`write_vectored`] until there is
+    /// no more data to be written or an error is returned.
+    ///
+    /// Note that `bufs` is modified by this method: on return it may contain
+    /// only the part that has not been written yet.
+    ///
+    /// [`write_vectored`]: Write::write_vectored
+    fn write_all_vectored(&mut self, mut bufs: &mut [IoSlice<'_>]) -> Result {
+        // Guarantee that bufs is empty if it contains no data,
+        // to avoid calling write_vectored if there is no data to be written.
+        IoSlice::advance_slices(&mut bufs, 0);
+        while !bufs.is_empty() {
+            match self.write_vectored(bufs) {
+                Ok(0) => ax_bail!(Io, "failed to write whole buffer"),
+                Ok(n) => IoSlice::advance_slices(&mut bufs, n),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
     /// Writes a formatted string into this writer, returning any error
     /// encountered.
     fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
@@ -278,6 +544,30 @@ pub trait Seek {
     fn stream_position(&mut self) -> Result<u64> {
         self.seek(SeekFrom::Current(0))
     }
+
+    /// Returns the length of this stream (in bytes).
+    ///
+    /// The default implementation seeks to the end and back, so it uses up to
+    /// three seek operations. Implementors that know their length should
+    /// override it.
+    fn stream_len(&mut self) -> Result<u64> {
+        let old_pos = self.stream_position()?;
+        let len = self.seek(SeekFrom::End(0))?;
+        if old_pos != len {
+            self.seek(SeekFrom::Start(old_pos))?;
+        }
+        Ok(len)
+    }
+
+    /// Seeks relative to the current position.
+    ///
+    /// This is equivalent to `self.seek(SeekFrom::Current(offset))` but does
+    /// not return the new position, which allows some implementations (such
+    /// as [`BufReader`]) to seek more efficiently.
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        self.seek(SeekFrom::Current(offset))?;
+        Ok(())
+    }
 }
 
 /// Enumeration of possible methods to seek within an I/O object.
@@ -303,6 +593,39 @@ pub enum SeekFrom {
     Current(i64),
 }
 
+impl SeekFrom {
+    /// Resolves this seek into an absolute position, given the `current`
+    /// position and the `len` of the object.
+    ///
+    /// Fails with [`InvalidInput`] if the result would be negative or would
+    /// overflow a `u64`.
+    ///
+    /// [`InvalidInput`]: Error::InvalidInput
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use axio::{Error, SeekFrom};
+    ///
+    /// assert_eq!(SeekFrom::End(-2).resolve(0, 10), Ok(8));
+    /// assert_eq!(SeekFrom::Current(-3).resolve(2, 10), Err(Error::InvalidInput));
+    /// ```
+    pub fn resolve(self, current: u64, len: u64) -> Result<u64> {
+        let (base, offset) = match self {
+            SeekFrom::Start(n) => return Ok(n),
+            SeekFrom::End(n) => (len, n),
+            SeekFrom::Current(n) => (current, n),
+        };
+        match base.checked_add_signed(offset) {
+            Some(n) => Ok(n),
+            None => ax_bail!(
+                InvalidInput,
+                "invalid seek to a negative or overflowing position"
+            ),
+        }
+    }
+}
+
 /// A `BufRead` is a type of `Read`er which has an internal buffer, allowing it
 /// to perform extra ways of reading.
 pub trait BufRead: Read {
@@ -320,16 +643,18 @@ pub trait BufRead: Read {
     }
 
     /// Read all bytes into `buf` until the delimiter `byte` or EOF is reached.
+    ///
+    /// Errors from [`fill_buf`], including [`WouldBlock`], are returned to
+    /// the caller; the bytes read before the error stay appended to `buf`.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    /// [`WouldBlock`]: Error::WouldBlock
     #[cfg(feature = "alloc")]
     fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
         let mut read = 0;
         loop {
             let (done, used) = {
-                let available = match self.fill_buf() {
-                    Ok(n) => n,
-                    Err(Error::WouldBlock) => continue,
-                    Err(e) => return Err(e),
-                };
+                let available = self.fill_buf()?;
                 match available.iter().position(|&b| b == byte) {
                     Some(i) => {
                         buf.extend_from_slice(&available[..=i]);
@@ -355,6 +680,64 @@ pub trait BufRead: Read {
     fn read_line(&mut self, buf: &mut String) -> Result<usize> {
         unsafe { append_to_string(buf, |b| self.read_until(b'\n', b)) }
     }
+
+    /// Skip all bytes until the delimiter `byte` or EOF is reached.
+    ///
+    /// The delimiter is consumed as well. Returns the number of bytes
+    /// skipped, including the delimiter if it was found. Unlike
+    /// [`read_until`], no data is copied or allocated.
+    ///
+    /// Errors from [`fill_buf`], including [`WouldBlock`], are returned to
+    /// the caller; the bytes skipped before the error stay consumed.
+    ///
+    /// [`read_until`]: BufRead::read_until
+    /// [`fill_buf`]: BufRead::fill_buf
+    /// [`WouldBlock`]: Error::WouldBlock
+    fn skip_until(&mut self, byte: u8) -> Result<usize> {
+        let mut read = 0;
+        loop {
+            let (done, used) = {
+                let available = self.fill_buf()?;
+                match available.iter().position(|&b| b == byte) {
+                    Some(i) => (true, i + 1),
+                    None => (false, available.len()),
+                }
+            };
+            self.consume(used);
+            read += used;
+            if done || used == 0 {
+                return Ok(read);
+            }
+        }
+    }
+
+    /// Returns an iterator over the contents of this reader split on the byte
+    /// `byte`.
+    ///
+    /// The iterator returned from this function will return instances of
+    /// <code>[Result]<[Vec]\<u8>></code>. Each vector returned will *not* have
+    /// the delimiter byte at the end.
+    #[cfg(feature = "alloc")]
+    fn split(self, byte: u8) -> Split<Self>
+    where
+        Self: Sized,
+    {
+        Split::new(self, byte)
+    }
+
+    /// Returns an iterator over the lines of this reader.
+    ///
+    /// The iterator returned from this function will yield instances of
+    /// <code>[Result]<[String]></code>. Each string returned will *not* have a
+    /// newline byte (the `0xA` byte) or `CRLF` (`0xD`, `0xA` bytes) at the
+    /// end.
+    #[cfg(feature = "alloc")]
+    fn lines(self) -> Lines<Self>
+    where
+        Self: Sized,
+    {
+        Lines::new(self)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -371,13 +754,3 @@ where
         Ok(ret)
     }
 }
-
-// FIXME: This is reserved for smooth migration of ArceOS.
-/// I/O poll results.
-#[derive(Debug, Default, Clone, Copy)]
-pub struct PollState {
-    /// Object can be read now.
-    pub readable: bool,
-    /// Object can be writen now.
-    pub writable: bool,
-}
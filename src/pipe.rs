@@ -0,0 +1,416 @@
+use core::{
+    cell::UnsafeCell,
+    cmp, fmt, hint,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    task::{Context, Waker},
+};
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use axerrno::ax_bail;
+
+use crate::{BufRead, IoEvents, Pollable, Read, Result, Write};
+
+/// Default number of bytes that a single write is guaranteed to put into a
+/// pipe atomically, as `PIPE_BUF` on Linux.
+pub const DEFAULT_PIPE_BUF: usize = 4096;
+
+/// Creates a new pipe able to hold `capacity` bytes, returning its read and
+/// write ends.
+///
+/// Writes of up to <code>min([DEFAULT_PIPE_BUF], capacity)</code> bytes are
+/// atomic. See [`pipe_with_pipe_buf`] to choose this limit.
+///
+/// # Errors
+///
+/// Fails with [`NoMemory`] if the buffer cannot be allocated.
+///
+/// # Panics
+///
+/// Panics if `capacity` is `0`.
+///
+/// [`NoMemory`]: crate::Error::NoMemory
+pub fn pipe(capacity: usize) -> Result<(PipeReader, PipeWriter)> {
+    pipe_with_pipe_buf(capacity, cmp::min(DEFAULT_PIPE_BUF, capacity))
+}
+
+/// Creates a new pipe able to hold `capacity` bytes, in which writes of up to
+/// `pipe_buf` bytes are atomic.
+///
+/// A write of at most `pipe_buf` bytes either puts all of its data into the
+/// pipe or fails with [`WouldBlock`]; it is never split. Larger writes may
+/// be partial.
+///
+/// # Errors
+///
+/// Fails with [`NoMemory`] if the buffer cannot be allocated.
+///
+/// # Panics
+///
+/// Panics if `capacity` is `0`, or if `pipe_buf` is greater than `capacity`.
+///
+/// [`WouldBlock`]: crate::Error::WouldBlock
+/// [`NoMemory`]: crate::Error::NoMemory
+pub fn pipe_with_pipe_buf(capacity: usize, pipe_buf: usize) -> Result<(PipeReader, PipeWriter)> {
+    assert!(capacity > 0, "pipe capacity must not be zero");
+    assert!(
+        pipe_buf <= capacity,
+        "atomic write size of a pipe must not exceed its capacity"
+    );
+
+    let mut buf = Vec::new();
+    buf.try_reserve_exact(capacity)
+        .map_err(|e| axerrno::ax_err_type!(NoMemory, e))?;
+    buf.resize_with(capacity, || UnsafeCell::new(0));
+
+    let shared = Arc::new(Shared {
+        buf: buf.into_boxed_slice(),
+        pipe_buf,
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        reader_alive: AtomicBool::new(true),
+        writer_alive: AtomicBool::new(true),
+        read_waker: WakerSlot::new(),
+        write_waker: WakerSlot::new(),
+    });
+    Ok((
+        PipeReader {
+            shared: shared.clone(),
+        },
+        PipeWriter { shared },
+    ))
+}
+
+/// State shared by the two ends of a pipe.
+///
+/// `head` and `tail` count the bytes ever read and written, modulo twice the
+/// capacity: this keeps the ring index continuous when they wrap around, for
+/// any capacity, while still telling a full buffer from an empty one. The
+/// bytes in `head..tail` (modulo the capacity) are owned by the reader, the
+/// rest of the buffer by the writer. Each index is only stored by its own
+/// end, with `Release` ordering, so that the other end observes the bytes it
+/// covers.
+struct Shared {
+    buf: Box<[UnsafeCell<u8>]>,
+    pipe_buf: usize,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    reader_alive: AtomicBool,
+    writer_alive: AtomicBool,
+    read_waker: WakerSlot,
+    write_waker: WakerSlot,
+}
+
+// SAFETY: the buffer is only accessed by the end that owns the region, as
+// described on `Shared`; everything else is atomic or locked.
+unsafe impl Send for Shared {}
+unsafe impl Sync for Shared {}
+
+impl Shared {
+    fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn ptr(&self) -> *mut u8 {
+        UnsafeCell::raw_get(self.buf.as_ptr())
+    }
+
+    /// Returns the position `amt` bytes after `pos`.
+    fn advance(&self, pos: usize, amt: usize) -> usize {
+        let wrap = 2 * self.capacity();
+        if amt >= wrap - pos {
+            amt - (wrap - pos)
+        } else {
+            pos + amt
+        }
+    }
+
+    /// Returns the number of bytes from `head` to `tail`.
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        if tail >= head {
+            tail - head
+        } else {
+            tail + 2 * self.capacity() - head
+        }
+    }
+
+    /// Returns the index in the buffer of the byte at `pos`.
+    fn index(&self, pos: usize) -> usize {
+        if pos >= self.capacity() {
+            pos - self.capacity()
+        } else {
+            pos
+        }
+    }
+}
+
+/// A waker protected by a spin lock.
+struct WakerSlot {
+    locked: AtomicBool,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+impl WakerSlot {
+    const fn new() -> WakerSlot {
+        WakerSlot {
+            locked: AtomicBool::new(false),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut Option<Waker>) -> T) -> T {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        // SAFETY: the lock is held.
+        let result = f(unsafe { &mut *self.waker.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+
+    fn register(&self, waker: &Waker) {
+        self.with(|slot| match slot {
+            Some(old) if old.will_wake(waker) => {}
+            _ => *slot = Some(waker.clone()),
+        })
+    }
+
+    fn wake(&self) {
+        if let Some(waker) = self.with(Option::take) {
+            waker.wake();
+        }
+    }
+}
+
+/// The read end of a pipe, created by [`pipe`].
+///
+/// Reading from an empty pipe fails with [`WouldBlock`] while the
+/// [`PipeWriter`] is alive, and returns EOF (`Ok(0)`) once it is dropped.
+///
+/// [`WouldBlock`]: crate::Error::WouldBlock
+pub struct PipeReader {
+    shared: Arc<Shared>,
+}
+
+impl PipeReader {
+    /// Returns the number of bytes the pipe can hold.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Returns the number of bytes that can be read right now.
+    pub fn len(&self) -> usize {
+        let tail = self.shared.tail.load(Ordering::Acquire);
+        let head = self.shared.head.load(Ordering::Relaxed);
+        self.shared.distance(head, tail)
+    }
+
+    /// Returns `true` if there is no data to read right now.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the write end has been dropped.
+    pub fn is_closed(&self) -> bool {
+        !self.shared.writer_alive.load(Ordering::Acquire)
+    }
+}
+
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = {
+            let mut data = self.fill_buf()?;
+            data.read(buf)?
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for PipeReader {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        // Check for EOF before loading `tail`, so that data written just
+        // before the writer was dropped is not missed.
+        let closed = self.is_closed();
+        let len = self.len();
+        if len == 0 {
+            if closed {
+                return Ok(&[]);
+            }
+            ax_bail!(WouldBlock);
+        }
+
+        let shared = &*self.shared;
+        let start = shared.index(shared.head.load(Ordering::Relaxed));
+        let contiguous = cmp::min(len, shared.capacity() - start);
+        // SAFETY: these bytes are in `head..tail`, which the writer does not
+        // touch until the reader moves `head` past them.
+        Ok(unsafe { core::slice::from_raw_parts(shared.ptr().add(start), contiguous) })
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = cmp::min(amt, self.len());
+        if amt > 0 {
+            let shared = &*self.shared;
+            let head = shared.head.load(Ordering::Relaxed);
+            shared
+                .head
+                .store(shared.advance(head, amt), Ordering::Release);
+            self.shared.write_waker.wake();
+        }
+    }
+}
+
+/// A `PipeReader` is readable when it has data, and reports [`HUP`] once the
+/// write end has been dropped.
+///
+/// [`HUP`]: IoEvents::HUP
+impl Pollable for PipeReader {
+    fn poll(&self) -> IoEvents {
+        let mut events = IoEvents::empty();
+        if self.is_closed() {
+            events |= IoEvents::HUP;
+        }
+        if !self.is_empty() {
+            events |= IoEvents::IN | IoEvents::RDNORM;
+        }
+        events
+    }
+
+    fn register(&self, context: &mut Context<'_>, _events: IoEvents) {
+        self.shared.read_waker.register(context.waker());
+    }
+}
+
+impl Drop for PipeReader {
+    fn drop(&mut self) {
+        self.shared.reader_alive.store(false, Ordering::Release);
+        self.shared.write_waker.wake();
+    }
+}
+
+impl fmt::Debug for PipeReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipeReader")
+            .field(
+                "buffer",
+                &format_args!("{}/{}", self.len(), self.capacity()),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+/// The write end of a pipe, created by [`pipe`].
+///
+/// Writing fails with [`BrokenPipe`] once the [`PipeReader`] is dropped, and
+/// with [`WouldBlock`] when the data does not fit (see
+/// [`pipe_with_pipe_buf`] for the atomicity rules).
+///
+/// [`BrokenPipe`]: crate::Error::BrokenPipe
+/// [`WouldBlock`]: crate::Error::WouldBlock
+pub struct PipeWriter {
+    shared: Arc<Shared>,
+}
+
+impl PipeWriter {
+    /// Returns the number of bytes the pipe can hold.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Returns the size up to which writes are atomic.
+    pub fn pipe_buf(&self) -> usize {
+        self.shared.pipe_buf
+    }
+
+    /// Returns the number of bytes that can be written right now.
+    pub fn free(&self) -> usize {
+        let head = self.shared.head.load(Ordering::Acquire);
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        self.capacity() - self.shared.distance(head, tail)
+    }
+
+    /// Returns `true` if the read end has been dropped.
+    pub fn is_closed(&self) -> bool {
+        !self.shared.reader_alive.load(Ordering::Acquire)
+    }
+}
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.is_closed() {
+            ax_bail!(BrokenPipe);
+        }
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let free = self.free();
+        if free == 0 || (buf.len() <= self.shared.pipe_buf && buf.len() > free) {
+            ax_bail!(WouldBlock);
+        }
+        let amt = cmp::min(buf.len(), free);
+
+        let shared = &*self.shared;
+        let tail = shared.tail.load(Ordering::Relaxed);
+        let start = shared.index(tail);
+        let first = cmp::min(amt, shared.capacity() - start);
+        // SAFETY: these bytes are outside `head..tail`, so the reader does not
+        // access them until the writer moves `tail` past them.
+        unsafe {
+            let ptr = shared.ptr();
+            ptr.add(start).copy_from_nonoverlapping(buf.as_ptr(), first);
+            ptr.copy_from_nonoverlapping(buf[first..].as_ptr(), amt - first);
+        }
+        shared
+            .tail
+            .store(shared.advance(tail, amt), Ordering::Release);
+        shared.read_waker.wake();
+        Ok(amt)
+    }
+
+    fn flush(&mut self) -> Result {
+        Ok(())
+    }
+}
+
+/// A `PipeWriter` is writable when an atomic write of [`pipe_buf`] bytes
+/// would succeed, and reports [`ERR`] once the read end has been dropped.
+///
+/// [`pipe_buf`]: PipeWriter::pipe_buf
+/// [`ERR`]: IoEvents::ERR
+impl Pollable for PipeWriter {
+    fn poll(&self) -> IoEvents {
+        if self.is_closed() {
+            return IoEvents::ERR;
+        }
+        if self.free() >= cmp::max(self.shared.pipe_buf, 1) {
+            IoEvents::OUT | IoEvents::WRNORM
+        } else {
+            IoEvents::empty()
+        }
+    }
+
+    fn register(&self, context: &mut Context<'_>, _events: IoEvents) {
+        self.shared.write_waker.register(context.waker());
+    }
+}
+
+impl Drop for PipeWriter {
+    fn drop(&mut self) {
+        self.shared.writer_alive.store(false, Ordering::Release);
+        self.shared.read_waker.wake();
+    }
+}
+
+impl fmt::Debug for PipeWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipeWriter")
+            .field("free", &self.free())
+            .field("capacity", &self.capacity())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,138 @@
+use core::task::Context;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use bitflags::bitflags;
+
+bitflags! {
+    /// I/O readiness events.
+    ///
+    /// The bit values are the ones used by Linux `poll(2)` and `epoll(7)`, so
+    /// an `IoEvents` converts losslessly to and from the `events`/`revents`
+    /// fields with [`bits`] and [`from_bits_retain`]. Unknown bits are kept
+    /// as-is.
+    ///
+    /// [`bits`]: IoEvents::bits
+    /// [`from_bits_retain`]: IoEvents::from_bits_retain
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct IoEvents: u32 {
+        /// There is data to read (`POLLIN`).
+        const IN = 0x0001;
+        /// There is an exceptional condition, e.g. out-of-band data
+        /// (`POLLPRI`).
+        const PRI = 0x0002;
+        /// Writing is now possible (`POLLOUT`).
+        const OUT = 0x0004;
+        /// Error condition (`POLLERR`).
+        const ERR = 0x0008;
+        /// Hang up: the peer closed its end (`POLLHUP`).
+        const HUP = 0x0010;
+        /// Invalid request: the object is not open (`POLLNVAL`).
+        const NVAL = 0x0020;
+        /// Normal data may be read (`POLLRDNORM`).
+        const RDNORM = 0x0040;
+        /// Priority band data may be read (`POLLRDBAND`).
+        const RDBAND = 0x0080;
+        /// Normal data may be written (`POLLWRNORM`).
+        const WRNORM = 0x0100;
+        /// Priority data may be written (`POLLWRBAND`).
+        const WRBAND = 0x0200;
+        /// Unused on Linux (`POLLMSG`).
+        const MSG = 0x0400;
+        /// Stream socket peer closed connection, or shut down writing half of
+        /// connection (`POLLRDHUP`).
+        const RDHUP = 0x2000;
+
+        /// Events that are always reported, whether requested or not.
+        const ALWAYS_POLL = Self::ERR.bits() | Self::HUP.bits() | Self::NVAL.bits();
+    }
+}
+
+/// An object whose I/O readiness can be queried.
+///
+/// This is the building block of `poll`/`select`/`epoll`: [`poll`] reports
+/// the current readiness, and [`register`] arranges for a task to be woken
+/// when it may change.
+///
+/// [`poll`]: Pollable::poll
+/// [`register`]: Pollable::register
+pub trait Pollable {
+    /// Returns the events that are currently ready on this object.
+    fn poll(&self) -> IoEvents;
+
+    /// Registers the waker of `context` to be woken when any of `events` may
+    /// have become ready.
+    ///
+    /// Wakeups may be spurious, so the caller should call [`poll`] again
+    /// after being woken.
+    ///
+    /// [`poll`]: Pollable::poll
+    fn register(&self, context: &mut Context<'_>, events: IoEvents);
+}
+
+impl<P: Pollable + ?Sized> Pollable for &P {
+    #[inline]
+    fn poll(&self) -> IoEvents {
+        (**self).poll()
+    }
+
+    #[inline]
+    fn register(&self, context: &mut Context<'_>, events: IoEvents) {
+        (**self).register(context, events)
+    }
+}
+
+impl<P: Pollable + ?Sized> Pollable for &mut P {
+    #[inline]
+    fn poll(&self) -> IoEvents {
+        (**self).poll()
+    }
+
+    #[inline]
+    fn register(&self, context: &mut Context<'_>, events: IoEvents) {
+        (**self).register(context, events)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Pollable + ?Sized> Pollable for Box<P> {
+    #[inline]
+    fn poll(&self) -> IoEvents {
+        (**self).poll()
+    }
+
+    #[inline]
+    fn register(&self, context: &mut Context<'_>, events: IoEvents) {
+        (**self).register(context, events)
+    }
+}
+
+/// I/O poll results.
+#[deprecated(note = "use `IoEvents` instead")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PollState {
+    /// Object can be read now.
+    pub readable: bool,
+    /// Object can be writen now.
+    pub writable: bool,
+}
+
+#[allow(deprecated)]
+impl From<IoEvents> for PollState {
+    fn from(events: IoEvents) -> Self {
+        PollState {
+            readable: events.contains(IoEvents::IN),
+            writable: events.contains(IoEvents::OUT),
+        }
+    }
+}
+
+#[allow(deprecated)]
+impl From<PollState> for IoEvents {
+    fn from(state: PollState) -> Self {
+        let mut events = IoEvents::empty();
+        events.set(IoEvents::IN, state.readable);
+        events.set(IoEvents::OUT, state.writable);
+        events
+    }
+}
@@ -0,0 +1,325 @@
+use core::cmp;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+use axerrno::ax_bail;
+
+use crate::{Cursor, IoSliceMut, Read, Result, Seek, SeekFrom};
+
+/// The `ReadAt` trait allows for reading bytes from a source at a given
+/// offset.
+///
+/// Unlike [`Read`], reading does not depend on nor update any position, so
+/// it takes `&self` and several readers can access the same object at once.
+/// This matches `pread(2)`, block devices and page caches.
+pub trait ReadAt {
+    /// Reads some bytes starting at `offset` into the specified buffer,
+    /// returning how many bytes were read.
+    ///
+    /// A return value of `0` means that `offset` is at or past the end of
+    /// the data, or that `buf` is empty.
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize>;
+
+    /// Reads the exact number of bytes required to fill `buf`, starting at
+    /// `offset`.
+    ///
+    /// If the end of the data is reached first, an [`Io`] error is returned
+    /// and the contents of `buf` are unspecified.
+    ///
+    /// [`Io`]: crate::Error::Io
+    fn read_exact_at(&self, mut offset: u64, mut buf: &mut [u8]) -> Result {
+        while !buf.is_empty() {
+            match self.read_at(offset, buf) {
+                Ok(0) => ax_bail!(Io, "failed to read whole buffer"),
+                Ok(n) => {
+                    let tmp = buf;
+                    buf = &mut tmp[n..];
+                    offset += n as u64;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the total size of the data, in bytes.
+    ///
+    /// The default implementation fails with [`Unsupported`].
+    ///
+    /// [`Unsupported`]: crate::Error::Unsupported
+    fn size(&self) -> Result<u64> {
+        ax_bail!(Unsupported, "size is not known");
+    }
+}
+
+/// The `WriteAt` trait allows for writing bytes to a sink at a given offset.
+///
+/// Unlike [`Write`], writing does not depend on nor update any position.
+/// This matches `pwrite(2)`. Objects that support concurrent writes through
+/// a shared reference can implement it for `&T`.
+///
+/// [`Write`]: crate::Write
+pub trait WriteAt {
+    /// Writes some bytes from `buf` starting at `offset`, returning how many
+    /// bytes were written.
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize>;
+
+    /// Writes the entire buffer starting at `offset`.
+    fn write_all_at(&mut self, mut offset: u64, mut buf: &[u8]) -> Result {
+        while !buf.is_empty() {
+            match self.write_at(offset, buf) {
+                Ok(0) => ax_bail!(Io, "failed to write whole buffer"),
+                Ok(n) => {
+                    buf = &buf[n..];
+                    offset += n as u64;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: ReadAt + ?Sized> ReadAt for &R {
+    #[inline]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize> {
+        (**self).read_at(offset, buf)
+    }
+
+    #[inline]
+    fn read_exact_at(&self, offset: u64, buf: &mut [u8]) -> Result {
+        (**self).read_exact_at(offset, buf)
+    }
+
+    #[inline]
+    fn size(&self) -> Result<u64> {
+        (**self).size()
+    }
+}
+
+impl<R: ReadAt + ?Sized> ReadAt for &mut R {
+    #[inline]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize> {
+        (**self).read_at(offset, buf)
+    }
+
+    #[inline]
+    fn read_exact_at(&self, offset: u64, buf: &mut [u8]) -> Result {
+        (**self).read_exact_at(offset, buf)
+    }
+
+    #[inline]
+    fn size(&self) -> Result<u64> {
+        (**self).size()
+    }
+}
+
+impl<W: WriteAt + ?Sized> WriteAt for &mut W {
+    #[inline]
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize> {
+        (**self).write_at(offset, buf)
+    }
+
+    #[inline]
+    fn write_all_at(&mut self, offset: u64, buf: &[u8]) -> Result {
+        (**self).write_all_at(offset, buf)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: ReadAt + ?Sized> ReadAt for Box<R> {
+    #[inline]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize> {
+        (**self).read_at(offset, buf)
+    }
+
+    #[inline]
+    fn read_exact_at(&self, offset: u64, buf: &mut [u8]) -> Result {
+        (**self).read_exact_at(offset, buf)
+    }
+
+    #[inline]
+    fn size(&self) -> Result<u64> {
+        (**self).size()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<W: WriteAt + ?Sized> WriteAt for Box<W> {
+    #[inline]
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize> {
+        (**self).write_at(offset, buf)
+    }
+
+    #[inline]
+    fn write_all_at(&mut self, offset: u64, buf: &[u8]) -> Result {
+        (**self).write_all_at(offset, buf)
+    }
+}
+
+impl ReadAt for [u8] {
+    #[inline]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize> {
+        let start = cmp::min(offset, self.len() as u64) as usize;
+        Read::read(&mut &self[start..], buf)
+    }
+
+    #[inline]
+    fn size(&self) -> Result<u64> {
+        Ok(self.len() as u64)
+    }
+}
+
+/// Writing past the end of the slice writes nothing, so [`write_all_at`]
+/// fails there.
+///
+/// [`write_all_at`]: WriteAt::write_all_at
+impl WriteAt for [u8] {
+    #[inline]
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize> {
+        let start = cmp::min(offset, self.len() as u64) as usize;
+        let amt = cmp::min(self.len() - start, buf.len());
+        self[start..start + amt].copy_from_slice(&buf[..amt]);
+        Ok(amt)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ReadAt for Vec<u8> {
+    #[inline]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize> {
+        self.as_slice().read_at(offset, buf)
+    }
+
+    #[inline]
+    fn size(&self) -> Result<u64> {
+        Ok(self.len() as u64)
+    }
+}
+
+/// Writing past the end of the vector grows it, zero-filling the gap between
+/// the old end and `offset`.
+#[cfg(feature = "alloc")]
+impl WriteAt for Vec<u8> {
+    #[inline]
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize> {
+        let mut pos = offset;
+        crate::cursor::vec_write(&mut pos, self, buf)
+    }
+}
+
+/// Positional reads ignore, and do not move, the position of the cursor.
+impl<T: ReadAt> ReadAt for Cursor<T> {
+    #[inline]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize> {
+        self.get_ref().read_at(offset, buf)
+    }
+
+    #[inline]
+    fn size(&self) -> Result<u64> {
+        self.get_ref().size()
+    }
+}
+
+/// Positional writes ignore, and do not move, the position of the cursor.
+impl<T: WriteAt> WriteAt for Cursor<T> {
+    #[inline]
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> Result<usize> {
+        self.get_mut().write_at(offset, buf)
+    }
+}
+
+/// A [`Read`] + [`Seek`] cursor over a [`ReadAt`] object.
+///
+/// Each `ReadAtCursor` keeps its own position, so several of them can read
+/// the same object independently, e.g. one per task over a shared `&R`.
+///
+/// Seeking relative to the end uses [`ReadAt::size`].
+#[derive(Debug, Clone)]
+pub struct ReadAtCursor<R> {
+    inner: R,
+    pos: u64,
+}
+
+impl<R> ReadAtCursor<R> {
+    /// Creates a new cursor over `inner`, starting at offset `0`.
+    pub const fn new(inner: R) -> ReadAtCursor<R> {
+        ReadAtCursor { inner, pos: 0 }
+    }
+
+    /// Gets a reference to the underlying object.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying object.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this cursor, returning the underlying object.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns the current position of this cursor.
+    pub const fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Sets the position of this cursor.
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+}
+
+impl<R: ReadAt> Read for ReadAtCursor<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read_at(self.pos, buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let mut nread = 0;
+        for buf in bufs {
+            // Once some bytes are read, report them rather than an error that
+            // the next call would run into anyway.
+            let n = match self.read(buf) {
+                Ok(n) => n,
+                Err(e) if nread == 0 => return Err(e),
+                Err(_) => break,
+            };
+            nread += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(nread)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result {
+        self.inner.read_exact_at(self.pos, buf)?;
+        self.pos += buf.len() as u64;
+        Ok(())
+    }
+}
+
+impl<R: ReadAt> Seek for ReadAtCursor<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let len = match pos {
+            SeekFrom::End(_) => self.inner.size()?,
+            _ => 0,
+        };
+        self.pos = pos.resolve(self.pos, len)?;
+        Ok(self.pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        self.inner.size()
+    }
+}
@@ -0,0 +1,309 @@
+use std::{fmt, io};
+
+use axerrno::AxErrorKind;
+
+use crate::{BufRead, Error, Read, Result, Seek, SeekFrom, Write};
+
+/// An [`Error`] carried inside a [`std::io::Error`], so that it can be
+/// recovered exactly.
+#[derive(Debug)]
+struct Wrapped(Error);
+
+impl fmt::Display for Wrapped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for Wrapped {}
+
+/// Returns the [`std::io::ErrorKind`] closest to `err`.
+///
+/// Errors with no counterpart in `std` map to [`ErrorKind::Other`].
+///
+/// [`ErrorKind::Other`]: io::ErrorKind::Other
+pub fn to_std_error_kind(err: Error) -> io::ErrorKind {
+    use io::ErrorKind as K;
+    use AxErrorKind::*;
+
+    let Ok(kind) = AxErrorKind::try_from(err) else {
+        return K::Other;
+    };
+    match kind {
+        AddrInUse => K::AddrInUse,
+        AlreadyExists => K::AlreadyExists,
+        ArgumentListTooLong => K::ArgumentListTooLong,
+        BrokenPipe => K::BrokenPipe,
+        ConnectionRefused => K::ConnectionRefused,
+        ConnectionReset => K::ConnectionReset,
+        CrossesDevices => K::CrossesDevices,
+        DirectoryNotEmpty => K::DirectoryNotEmpty,
+        Interrupted => K::Interrupted,
+        InvalidData => K::InvalidData,
+        InvalidInput => K::InvalidInput,
+        IsADirectory => K::IsADirectory,
+        NoMemory => K::OutOfMemory,
+        NotADirectory => K::NotADirectory,
+        NotConnected => K::NotConnected,
+        NotFound => K::NotFound,
+        OperationNotPermitted | PermissionDenied => K::PermissionDenied,
+        OperationNotSupported | Unsupported => K::Unsupported,
+        ReadOnlyFilesystem => K::ReadOnlyFilesystem,
+        ResourceBusy => K::ResourceBusy,
+        StorageFull => K::StorageFull,
+        TimedOut => K::TimedOut,
+        UnexpectedEof => K::UnexpectedEof,
+        WouldBlock => K::WouldBlock,
+        WriteZero => K::WriteZero,
+        _ => K::Other,
+    }
+}
+
+/// Returns the [`Error`] closest to `kind`.
+///
+/// Kinds with no counterpart map to [`Error::Io`].
+pub fn from_std_error_kind(kind: io::ErrorKind) -> Error {
+    use io::ErrorKind as K;
+
+    match kind {
+        K::AddrInUse => Error::AddrInUse,
+        K::AlreadyExists => Error::AlreadyExists,
+        K::ArgumentListTooLong => Error::ArgumentListTooLong,
+        K::BrokenPipe => Error::BrokenPipe,
+        K::ConnectionRefused => Error::ConnectionRefused,
+        K::ConnectionReset => Error::ConnectionReset,
+        K::CrossesDevices => Error::CrossesDevices,
+        K::DirectoryNotEmpty => Error::DirectoryNotEmpty,
+        K::Interrupted => Error::Interrupted,
+        K::InvalidData => Error::InvalidData,
+        K::InvalidInput | K::InvalidFilename => Error::InvalidInput,
+        K::IsADirectory => Error::IsADirectory,
+        K::NotADirectory => Error::NotADirectory,
+        K::NotConnected => Error::NotConnected,
+        K::NotFound => Error::NotFound,
+        K::OutOfMemory => Error::NoMemory,
+        K::PermissionDenied => Error::PermissionDenied,
+        K::ReadOnlyFilesystem => Error::ReadOnlyFilesystem,
+        K::ResourceBusy => Error::ResourceBusy,
+        K::StorageFull => Error::StorageFull,
+        K::TimedOut => Error::TimedOut,
+        K::UnexpectedEof => Error::UnexpectedEof,
+        K::Unsupported => Error::Unsupported,
+        K::WouldBlock => Error::WouldBlock,
+        K::WriteZero => Error::WriteZero,
+        _ => Error::Io,
+    }
+}
+
+/// Converts `err` into a [`std::io::Error`].
+///
+/// The result has the kind given by [`to_std_error_kind`] and carries `err`
+/// itself, which [`from_std_error`] gets back unchanged.
+pub fn to_std_error(err: Error) -> io::Error {
+    io::Error::new(to_std_error_kind(err), Wrapped(err))
+}
+
+/// Converts a [`std::io::Error`] into an [`Error`].
+///
+/// Errors made by [`to_std_error`] give back the original error. OS errors
+/// on Linux keep their errno; anything else is mapped by
+/// [`from_std_error_kind`].
+pub fn from_std_error(err: &io::Error) -> Error {
+    if let Some(Wrapped(err)) = err.get_ref().and_then(|e| e.downcast_ref()) {
+        return *err;
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    if let Some(errno) = err.raw_os_error() {
+        if let Ok(err) = axerrno::LinuxError::try_from(errno) {
+            return Error::from(err).canonicalize();
+        }
+    }
+    from_std_error_kind(err.kind())
+}
+
+impl From<SeekFrom> for io::SeekFrom {
+    fn from(pos: SeekFrom) -> Self {
+        match pos {
+            SeekFrom::Start(n) => io::SeekFrom::Start(n),
+            SeekFrom::End(n) => io::SeekFrom::End(n),
+            SeekFrom::Current(n) => io::SeekFrom::Current(n),
+        }
+    }
+}
+
+impl From<io::SeekFrom> for SeekFrom {
+    fn from(pos: io::SeekFrom) -> Self {
+        match pos {
+            io::SeekFrom::Start(n) => SeekFrom::Start(n),
+            io::SeekFrom::End(n) => SeekFrom::End(n),
+            io::SeekFrom::Current(n) => SeekFrom::Current(n),
+        }
+    }
+}
+
+/// An adapter that implements the `axio` traits for a [`std::io`] object.
+///
+/// Errors are converted with [`from_std_error`].
+#[derive(Debug, Default, Clone)]
+pub struct FromStd<T> {
+    inner: T,
+}
+
+impl<T> FromStd<T> {
+    /// Wraps a [`std::io`] object.
+    pub const fn new(inner: T) -> FromStd<T> {
+        FromStd { inner }
+    }
+
+    /// Gets a reference to the underlying object.
+    pub const fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying object.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps this `FromStd`, returning the underlying object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: io::Read> Read for FromStd<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.inner.read(buf).map_err(|e| from_std_error(&e))
+    }
+
+    /// Fails with [`Io`] at EOF, like every other `read_exact` in `axio`,
+    /// rather than with the `UnexpectedEof` reported by [`std::io`].
+    ///
+    /// [`Io`]: Error::Io
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result {
+        self.inner.read_exact(buf).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => {
+                axerrno::ax_err_type!(Io, "failed to fill whole buffer")
+            }
+            _ => from_std_error(&e),
+        })
+    }
+}
+
+impl<T: io::Write> Write for FromStd<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.inner.write(buf).map_err(|e| from_std_error(&e))
+    }
+
+    fn flush(&mut self) -> Result {
+        self.inner.flush().map_err(|e| from_std_error(&e))
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result {
+        self.inner.write_all(buf).map_err(|e| from_std_error(&e))
+    }
+}
+
+impl<T: io::Seek> Seek for FromStd<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos.into()).map_err(|e| from_std_error(&e))
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.inner.stream_position().map_err(|e| from_std_error(&e))
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        self.inner
+            .seek_relative(offset)
+            .map_err(|e| from_std_error(&e))
+    }
+}
+
+impl<T: io::BufRead> BufRead for FromStd<T> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.inner.fill_buf().map_err(|e| from_std_error(&e))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+/// An adapter that implements the [`std::io`] traits for an `axio` object.
+///
+/// Errors are converted with [`to_std_error`].
+#[derive(Debug, Default, Clone)]
+pub struct ToStd<T> {
+    inner: T,
+}
+
+impl<T> ToStd<T> {
+    /// Wraps an `axio` object.
+    pub const fn new(inner: T) -> ToStd<T> {
+        ToStd { inner }
+    }
+
+    /// Gets a reference to the underlying object.
+    pub const fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying object.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps this `ToStd`, returning the underlying object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Read> io::Read for ToStd<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf).map_err(to_std_error)
+    }
+
+    // `read_exact` is left to the default implementation, which loops over
+    // `read` and fails with `UnexpectedEof` at EOF as std callers expect,
+    // whereas `axio` readers fail with `Io`.
+}
+
+impl<T: Write> io::Write for ToStd<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf).map_err(to_std_error)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().map_err(to_std_error)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.inner.write_all(buf).map_err(to_std_error)
+    }
+}
+
+impl<T: Seek> io::Seek for ToStd<T> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos.into()).map_err(to_std_error)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.inner.stream_position().map_err(to_std_error)
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        self.inner.seek_relative(offset).map_err(to_std_error)
+    }
+}
+
+impl<T: BufRead> io::BufRead for ToStd<T> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf().map_err(to_std_error)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
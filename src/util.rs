@@ -0,0 +1,320 @@
+use core::{fmt, task::Context};
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    BorrowedCursor, BufRead, IoEvents, IoSlice, IoSliceMut, Pollable, Read, Result, Seek, SeekFrom,
+    Write,
+};
+
+/// `Empty` ignores any data written via [`Write`], and will always be empty
+/// (returning zero bytes) when read via [`Read`].
+///
+/// This struct is generally created by calling [`empty()`]. Please
+/// see the documentation of [`empty()`] for more details.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Empty;
+
+/// Creates a value that is always at EOF for reads, and ignores all data
+/// written.
+///
+/// All calls to [`write`] on the returned instance will return `Ok(buf.len())`
+/// and the contents of the buffer will not be inspected.
+///
+/// All calls to [`read`] from the returned reader will return `Ok(0)`.
+///
+/// This is handy for `/dev/null`-like objects.
+///
+/// [`write`]: Write::write
+/// [`read`]: Read::read
+pub const fn empty() -> Empty {
+    Empty
+}
+
+impl Read for Empty {
+    #[inline]
+    fn read(&mut self, _buf: &mut [u8]) -> Result<usize> {
+        Ok(0)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, _bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        Ok(0)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        // Do not force `Chain<Empty, T>` or `Chain<T, Empty>` to use vectored
+        // reads, unless the other reader is vectored.
+        false
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        if !buf.is_empty() {
+            axerrno::ax_bail!(Io, "failed to fill whole buffer");
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn read_buf(&mut self, _cursor: BorrowedCursor<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn read_buf_exact(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        if cursor.capacity() != 0 {
+            axerrno::ax_bail!(Io, "failed to fill whole buffer");
+        }
+        Ok(())
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_to_end(&mut self, _buf: &mut Vec<u8>) -> Result<usize> {
+        Ok(0)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_to_string(&mut self, _buf: &mut String) -> Result<usize> {
+        Ok(0)
+    }
+}
+
+impl BufRead for Empty {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(&[])
+    }
+
+    #[inline]
+    fn consume(&mut self, _n: usize) {}
+
+    #[inline]
+    fn has_data_left(&mut self) -> Result<bool> {
+        Ok(false)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_until(&mut self, _byte: u8, _buf: &mut Vec<u8>) -> Result<usize> {
+        Ok(0)
+    }
+
+    #[inline]
+    fn skip_until(&mut self, _byte: u8) -> Result<usize> {
+        Ok(0)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_line(&mut self, _buf: &mut String) -> Result<usize> {
+        Ok(0)
+    }
+}
+
+impl Seek for Empty {
+    fn seek(&mut self, _pos: SeekFrom) -> Result<u64> {
+        Ok(0)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(0)
+    }
+}
+
+impl Write for Empty {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let total_len = bufs.iter().map(|b| b.len()).sum();
+        Ok(total_len)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn write_all(&mut self, _buf: &[u8]) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn write_all_vectored(&mut self, _bufs: &mut [IoSlice<'_>]) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, _fmt: fmt::Arguments<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Pollable for Empty {
+    fn poll(&self) -> IoEvents {
+        IoEvents::IN | IoEvents::RDNORM | IoEvents::OUT | IoEvents::WRNORM
+    }
+
+    fn register(&self, _context: &mut Context<'_>, _events: IoEvents) {}
+}
+
+/// A reader which yields one byte over and over and over and over and over
+/// and...
+///
+/// This struct is generally created by calling [`repeat()`]. Please
+/// see the documentation of [`repeat()`] for more details.
+#[derive(Copy, Clone, Debug)]
+pub struct Repeat {
+    byte: u8,
+}
+
+/// Creates an instance of a reader that infinitely repeats one byte.
+///
+/// All reads from this reader will succeed by filling the specified buffer
+/// with the given byte. This is handy for `/dev/zero`-like objects.
+pub const fn repeat(byte: u8) -> Repeat {
+    Repeat { byte }
+}
+
+impl Read for Repeat {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        buf.fill(self.byte);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let mut nwritten = 0;
+        for buf in bufs {
+            nwritten += self.read(buf)?;
+        }
+        Ok(nwritten)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        buf.fill(self.byte);
+        Ok(())
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        // SAFETY: No uninit bytes are being written.
+        for slot in unsafe { cursor.as_mut() } {
+            slot.write(self.byte);
+        }
+        // SAFETY: the entire unfilled portion of cursor has been initialized.
+        unsafe { cursor.advance_unchecked(cursor.capacity()) };
+        Ok(())
+    }
+
+    fn read_buf_exact(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
+        self.read_buf(cursor)
+    }
+
+    /// This function is not supported by `Repeat`, because there's no end of
+    /// its data
+    #[cfg(feature = "alloc")]
+    fn read_to_end(&mut self, _: &mut Vec<u8>) -> Result<usize> {
+        axerrno::ax_bail!(NoMemory, "cannot read an infinite stream to the end");
+    }
+
+    /// This function is not supported by `Repeat`, because there's no end of
+    /// its data
+    #[cfg(feature = "alloc")]
+    fn read_to_string(&mut self, _: &mut String) -> Result<usize> {
+        axerrno::ax_bail!(NoMemory, "cannot read an infinite stream to the end");
+    }
+}
+
+impl Pollable for Repeat {
+    fn poll(&self) -> IoEvents {
+        IoEvents::IN | IoEvents::RDNORM
+    }
+
+    fn register(&self, _context: &mut Context<'_>, _events: IoEvents) {}
+}
+
+/// A writer which will move data into the void.
+///
+/// This struct is generally created by calling [`sink()`]. Please
+/// see the documentation of [`sink()`] for more details.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Sink;
+
+/// Creates an instance of a writer which will successfully consume all data.
+///
+/// All calls to [`write`] on the returned instance will return `Ok(buf.len())`
+/// and the contents of the buffer will not be inspected.
+///
+/// [`write`]: Write::write
+pub const fn sink() -> Sink {
+    Sink
+}
+
+impl Write for Sink {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let total_len = bufs.iter().map(|b| b.len()).sum();
+        Ok(total_len)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn write_all(&mut self, _buf: &[u8]) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn write_all_vectored(&mut self, _bufs: &mut [IoSlice<'_>]) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, _fmt: fmt::Arguments<'_>) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Pollable for Sink {
+    fn poll(&self) -> IoEvents {
+        IoEvents::OUT | IoEvents::WRNORM
+    }
+
+    fn register(&self, _context: &mut Context<'_>, _events: IoEvents) {}
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "alloc")]
+
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+    task::{Context, Poll, Waker},
+};
+
+use axio::{
+    async_io::{AsyncReadExt, AsyncWriteExt},
+    AsyncBufRead, AsyncBufReader, Error,
+};
+
+mod common;
+
+use common::Mock;
+
+/// Polls `fut` with a no-op waker until it completes, returning the result
+/// and how many times it was pending.
+fn block_on<F: Future>(fut: F) -> (F::Output, usize) {
+    let mut fut = pin!(fut);
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut pending = 0;
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(out) => return (out, pending),
+            Poll::Pending => pending += 1,
+        }
+    }
+}
+
+#[test]
+fn read_exact_across_pending() {
+    let mut reader = Mock::stutter(b"hello world", 3);
+    let mut buf = [0; 8];
+    let (res, pending) = block_on(reader.read_exact(&mut buf));
+    res.unwrap();
+    assert_eq!(&buf, b"hello wo");
+    assert_eq!(pending, 3);
+}
+
+#[test]
+fn read_exact_fails_at_eof() {
+    let mut reader = Mock::stutter(b"abc", 2);
+    let mut buf = [0; 4];
+    let (res, _) = block_on(reader.read_exact(&mut buf));
+    assert_eq!(res.unwrap_err(), Error::Io);
+}
+
+#[test]
+fn read_to_end_small_chunks() {
+    let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+    let mut reader = Mock::stutter(&data, 7);
+    let mut buf = b"head".to_vec();
+    let (res, _) = block_on(reader.read_to_end(&mut buf));
+    assert_eq!(res.unwrap(), 1000);
+    assert_eq!(&buf[..4], b"head");
+    assert_eq!(&buf[4..], data);
+}
+
+#[test]
+fn write_all_short_writes() {
+    let mut writer = Mock::stutter(b"", 3);
+    let (res, pending) = block_on(writer.write_all(b"hello world"));
+    res.unwrap();
+    assert_eq!(writer.data, b"hello world");
+    assert_eq!(pending, 4);
+}
+
+#[test]
+fn write_all_fails_on_zero_write() {
+    let mut writer = Mock::stutter(b"", 0);
+    let (res, _) = block_on(writer.write_all(b"abc"));
+    assert_eq!(res.unwrap_err(), Error::Io);
+}
+
+#[test]
+fn buf_reader_fill_and_consume() {
+    let mut reader = AsyncBufReader::<_, 4>::new_inline(Mock::stutter(b"abcdefgh", 8));
+
+    let (res, pending) = block_on(std::future::poll_fn(|cx| {
+        Pin::new(&mut reader)
+            .poll_fill_buf(cx)
+            .map_ok(<[u8]>::to_vec)
+    }));
+    assert_eq!(res.unwrap(), b"abcd");
+    assert_eq!(pending, 1);
+
+    Pin::new(&mut reader).consume(3);
+    assert_eq!(reader.buffer(), b"d");
+
+    // Buffered bytes are served before the inner reader is polled again.
+    let mut buf = [0; 8];
+    let (res, pending) = block_on(reader.read(&mut buf));
+    assert_eq!(res.unwrap(), 1);
+    assert_eq!(&buf[..1], b"d");
+    assert_eq!(pending, 0);
+
+    let mut rest = Vec::new();
+    let (res, _) = block_on(reader.read_to_end(&mut rest));
+    assert_eq!(res.unwrap(), 4);
+    assert_eq!(rest, b"efgh");
+}
+
+mod glob {
+    use axio::*;
+
+    #[test]
+    fn sync_methods_are_not_ambiguous() {
+        let mut cursor = Cursor::new(b"abc".to_vec());
+        let mut buf = [0; 3];
+        cursor.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"abc");
+
+        let mut out = Vec::new();
+        out.write_all(b"xyz").unwrap();
+        assert_eq!(out, b"xyz");
+    }
+}
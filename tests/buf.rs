@@ -0,0 +1,221 @@
+#![cfg(feature = "alloc")]
+
+use std::collections::VecDeque;
+
+use axio::{read_into_buf, transfer, write_from_buf, Buf, BufMut, BufRead, Chain, Cursor, Error};
+
+mod common;
+
+use common::Mock;
+
+#[test]
+fn consume_with_is_not_ambiguous() {
+    // `Buf::consume_with` and `BufRead::consume` can both be called on a type
+    // implementing both traits.
+    let mut cursor = Cursor::new(b"abc".to_vec());
+    cursor.consume(1);
+    assert_eq!(cursor.remaining(), 2);
+    assert_eq!(cursor.consume_with(|chunk| Ok(chunk.len())).unwrap(), 2);
+    assert_eq!(cursor.fill_buf().unwrap(), b"");
+}
+
+#[test]
+fn consume_with_stops_at_short_take() {
+    let mut src = &b"abcdef"[..];
+    assert_eq!(src.consume_with(|_| Ok(2)).unwrap(), 2);
+    assert_eq!(src, b"cdef");
+}
+
+#[test]
+fn consume_with_keeps_progress_on_error() {
+    let mut deque: VecDeque<u8> = VecDeque::with_capacity(8);
+    deque.extend(b"efgh");
+    deque.push_front(b'd');
+    let mut src = Chain::new(&b"abc"[..], deque);
+
+    let mut calls = 0;
+    let res = src.consume_with(|chunk| {
+        calls += 1;
+        if calls == 1 {
+            Ok(chunk.len())
+        } else {
+            Err(Error::BrokenPipe)
+        }
+    });
+    assert_eq!(res.unwrap(), 3);
+    assert_eq!(src.remaining(), 5);
+
+    // Nothing was moved before the error this time, so it is returned.
+    assert_eq!(
+        src.consume_with(|_| Err(Error::BrokenPipe)).unwrap_err(),
+        Error::BrokenPipe
+    );
+    assert_eq!(src.remaining(), 5);
+}
+
+#[test]
+fn put_with_stops_at_short_put() {
+    let mut out = [0; 6];
+    let mut dst = &mut out[..];
+    let n = dst
+        .put_with(|chunk| {
+            chunk[..2].copy_from_slice(b"ab");
+            Ok(2)
+        })
+        .unwrap();
+    assert_eq!(n, 2);
+    assert_eq!(dst.remaining_mut(), 4);
+    assert_eq!(&out[..2], b"ab");
+}
+
+#[test]
+fn transfer_respects_limit_and_chunks() {
+    let mut src = Chain::new(&b"abc"[..], &b"defgh"[..]);
+    let mut first = [0; 2];
+    let mut second = [0; 8];
+    let mut dst = Chain::new(&mut first[..], &mut second[..]);
+
+    assert_eq!(transfer(&mut src, &mut dst, 6), 6);
+    assert_eq!(src.remaining(), 2);
+    assert_eq!(dst.remaining_mut(), 4);
+    assert_eq!(transfer(&mut src, &mut dst, usize::MAX), 2);
+    assert_eq!(transfer(&mut src, &mut dst, usize::MAX), 0);
+
+    assert_eq!(&first, b"ab");
+    assert_eq!(&second[..6], b"cdefgh");
+}
+
+#[test]
+fn read_into_buf_stops_at_short_read() {
+    let mut reader = Mock::new(b"0123456789", 3);
+    let mut out = [0; 8];
+    let mut dst = &mut out[..];
+    assert_eq!(read_into_buf(&mut reader, &mut dst, usize::MAX).unwrap(), 3);
+    assert_eq!(read_into_buf(&mut reader, &mut dst, 2).unwrap(), 2);
+    assert_eq!(dst.remaining_mut(), 3);
+    assert_eq!(&out[..5], b"01234");
+}
+
+#[test]
+fn read_into_buf_keeps_progress_on_error() {
+    let mut reader = Mock::failing(b"0123456789", 3, 3, Error::WouldBlock);
+
+    // The first chunk is filled, then the error on the second chunk is dropped
+    // in favour of the partial count. The next call reports it.
+    let mut first = [0; 3];
+    let mut second = [0; 4];
+    let mut dst = Chain::new(&mut first[..], &mut second[..]);
+    assert_eq!(read_into_buf(&mut reader, &mut dst, usize::MAX).unwrap(), 3);
+    assert_eq!(
+        read_into_buf(&mut reader, &mut dst, usize::MAX).unwrap_err(),
+        Error::WouldBlock
+    );
+    assert_eq!(dst.remaining_mut(), 4);
+    assert_eq!(&first, b"012");
+}
+
+#[test]
+fn write_from_buf_consumes_only_accepted_bytes() {
+    let mut src = Cursor::new(b"0123456789");
+    let mut writer = Mock::new(b"", 4);
+    assert_eq!(
+        write_from_buf(&mut src, &mut writer, usize::MAX).unwrap(),
+        4
+    );
+    assert_eq!(src.remaining(), 6);
+
+    writer.budget = 0;
+    writer.fail = Error::BrokenPipe;
+    assert_eq!(
+        write_from_buf(&mut src, &mut writer, usize::MAX).unwrap_err(),
+        Error::BrokenPipe
+    );
+    assert_eq!(src.remaining(), 6);
+    assert_eq!(writer.data, b"0123");
+}
+
+#[test]
+fn write_from_buf_keeps_progress_on_error() {
+    let mut src = Chain::new(&b"abc"[..], &b"def"[..]);
+    let mut writer = Mock::failing(b"", usize::MAX, 3, Error::BrokenPipe);
+
+    // The whole first chunk is written, then the error on the second chunk is
+    // dropped in favour of the partial count.
+    assert_eq!(
+        write_from_buf(&mut src, &mut writer, usize::MAX).unwrap(),
+        3
+    );
+    assert_eq!(src.remaining(), 3);
+    assert_eq!(writer.data, b"abc");
+}
+
+/// A deque whose contents wrap around the end of its buffer.
+fn wrapped_deque() -> VecDeque<u8> {
+    let mut deque = VecDeque::with_capacity(8);
+    deque.extend(b"xxxxxab");
+    deque.drain(..5);
+    deque
+}
+
+#[test]
+fn vec_deque_short_put() {
+    let mut deque = wrapped_deque();
+    let n = deque
+        .put_with(|chunk| {
+            chunk[..2].copy_from_slice(b"cd");
+            Ok(2)
+        })
+        .unwrap();
+    assert_eq!(n, 2);
+    assert_eq!(deque, b"abcd");
+
+    // Nothing is left behind by a failed put either.
+    assert_eq!(
+        deque.put_with(|_| Err(Error::WouldBlock)).unwrap_err(),
+        Error::WouldBlock
+    );
+    assert_eq!(deque, b"abcd");
+}
+
+#[test]
+fn vec_deque_read_into_buf() {
+    let mut reader = Mock::new(b"0123456789", 3);
+    let mut deque = wrapped_deque();
+    assert_eq!(
+        read_into_buf(&mut reader, &mut deque, usize::MAX).unwrap(),
+        3
+    );
+    assert_eq!(read_into_buf(&mut reader, &mut deque, 2).unwrap(), 2);
+    assert_eq!(deque, b"ab01234");
+
+    // A failure after a short read still leaves only the bytes read.
+    let mut reader = Mock::failing(b"56789", 4, 4, Error::BrokenPipe);
+    assert_eq!(
+        read_into_buf(&mut reader, &mut deque, usize::MAX).unwrap(),
+        4
+    );
+    assert_eq!(
+        read_into_buf(&mut reader, &mut deque, usize::MAX).unwrap_err(),
+        Error::BrokenPipe
+    );
+    assert_eq!(deque, b"ab012345678");
+}
+
+#[test]
+fn vec_deque_transfer() {
+    let mut src = Chain::new(&b"abc"[..], &b"defgh"[..]);
+    let mut deque = wrapped_deque();
+    assert_eq!(transfer(&mut src, &mut deque, 4), 4);
+    assert_eq!(deque, b"ababcd");
+
+    let large = vec![7; 3000];
+    assert_eq!(transfer(&mut &large[..], &mut deque, usize::MAX), 3000);
+    assert_eq!(deque.len(), 3006);
+    assert!(deque.range(6..).all(|&b| b == 7));
+
+    // The deque can be drained through `Buf` again.
+    let mut out = Vec::new();
+    deque.truncate(8);
+    assert_eq!(write_from_buf(&mut deque, &mut out, usize::MAX).unwrap(), 8);
+    assert_eq!(out, b"ababcd\x07\x07");
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "alloc")]
+
+use axio::{BufRead, Error};
+
+mod common;
+
+use common::Mock;
+
+#[test]
+fn skip_until_returns_would_block() {
+    let mut reader = Mock::failing(b"abcde\nf", 3, 3, Error::WouldBlock);
+    assert_eq!(reader.skip_until(b'\n').unwrap_err(), Error::WouldBlock);
+
+    // The bytes before the error were skipped; once the source is ready again
+    // skipping picks up where it stopped.
+    reader.budget = usize::MAX;
+    assert_eq!(reader.skip_until(b'\n').unwrap(), 3);
+    assert_eq!(reader.fill_buf().unwrap(), b"f");
+}
+
+#[test]
+fn skip_until_stops_at_eof() {
+    let mut reader = Mock::new(b"abcd", 2);
+    assert_eq!(reader.skip_until(b'\n').unwrap(), 4);
+    assert_eq!(reader.skip_until(b'\n').unwrap(), 0);
+}
+
+#[test]
+fn read_until_returns_would_block() {
+    let mut reader = Mock::failing(b"ab\ncd", 1, 2, Error::WouldBlock);
+    let mut buf = Vec::new();
+    assert_eq!(
+        reader.read_until(b'\n', &mut buf).unwrap_err(),
+        Error::WouldBlock
+    );
+    // The bytes read before the error are kept.
+    assert_eq!(buf, b"ab");
+
+    reader.budget = usize::MAX;
+    assert_eq!(reader.read_until(b'\n', &mut buf).unwrap(), 1);
+    assert_eq!(buf, b"ab\n");
+}
+
+#[test]
+fn lines_and_split_return_would_block() {
+    let (reader, _writer) = axio::pipe(16).unwrap();
+    let mut lines = reader.lines();
+    assert_eq!(lines.next().unwrap().unwrap_err(), Error::WouldBlock);
+
+    let mut reader = Mock::new(b"a,b", usize::MAX);
+    reader.blocked = true;
+    let mut split = reader.split(b',');
+    assert_eq!(split.next().unwrap().unwrap_err(), Error::WouldBlock);
+}
+
+#[test]
+fn lines_strip_line_endings() {
+    let reader = Mock::new(b"one\r\ntwo\n\r\nthree\rfour\r", 2);
+    let lines: Vec<String> = reader.lines().map(Result::unwrap).collect();
+    assert_eq!(lines, ["one", "two", "", "three\rfour\r"]);
+}
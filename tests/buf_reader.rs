@@ -0,0 +1,155 @@
+#![cfg(feature = "alloc")]
+
+use axio::{BufRead, BufReader, Cursor, Error, Read, Seek, SeekFrom};
+
+mod common;
+
+use common::Mock;
+
+const DATA: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCD";
+
+/// Checks `read_exact` for every combination of already-consumed bytes,
+/// buffered-but-unconsumed bytes and request length.
+fn check_all_splits<const N: usize>(make: impl Fn(Mock) -> BufReader<Mock, N>) {
+    for chunk in 1..=DATA.len() {
+        for consumed in 0..=DATA.len() {
+            for want in 0..=DATA.len() - consumed {
+                let mut reader = make(Mock::new(DATA, chunk));
+
+                // Bring the reader to the state under test: `consumed` bytes
+                // read, with whatever the last refill left in the buffer.
+                let mut skipped = 0;
+                while skipped < consumed {
+                    let available = reader.fill_buf().unwrap().len();
+                    let amt = available.min(consumed - skipped);
+                    reader.consume(amt);
+                    skipped += amt;
+                }
+
+                let mut buf = vec![0; want];
+                reader.read_exact(&mut buf).unwrap();
+                assert_eq!(
+                    buf,
+                    &DATA[consumed..consumed + want],
+                    "chunk={chunk} consumed={consumed} want={want}"
+                );
+
+                let mut rest = Vec::new();
+                reader.read_to_end(&mut rest).unwrap();
+                assert_eq!(
+                    rest,
+                    &DATA[consumed + want..],
+                    "chunk={chunk} consumed={consumed} want={want}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn read_exact_inline_buffer() {
+    check_all_splits(BufReader::<_, 8>::new_inline);
+}
+
+#[test]
+fn read_exact_default_buffer() {
+    check_all_splits(BufReader::new);
+}
+
+#[test]
+fn read_exact_heap_buffer() {
+    check_all_splits(|inner| BufReader::with_capacity(5, inner));
+}
+
+#[test]
+fn read_exact_partially_buffered() {
+    let mut reader = BufReader::<_, 8>::new_inline(Mock::new(DATA, 8));
+    reader.fill_buf().unwrap();
+    reader.consume(5);
+    assert_eq!(reader.buffer(), &DATA[5..8]);
+
+    // 3 bytes are buffered; the request spans the buffer and the inner reader.
+    let mut buf = [0; 10];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, DATA[5..15]);
+
+    // A request larger than the capacity bypasses the buffer once it is empty.
+    let mut buf = [0; 20];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, DATA[15..35]);
+}
+
+#[test]
+fn read_exact_eof() {
+    let mut reader = BufReader::<_, 8>::new_inline(Mock::new(DATA, 3));
+    reader.fill_buf().unwrap();
+
+    let mut buf = vec![0; DATA.len() + 1];
+    assert_eq!(reader.read_exact(&mut buf), Err(Error::Io));
+}
+
+#[test]
+fn zero_capacity_is_rounded_up() {
+    let mut reader = BufReader::with_capacity(0, Mock::new(DATA, 8));
+    assert_eq!(reader.capacity(), 1);
+    assert_eq!(reader.fill_buf().unwrap(), b"0");
+
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, DATA);
+}
+
+#[test]
+fn seek_relative_within_buffer() {
+    let mut reader = BufReader::<_, 8>::new_inline(Cursor::new(DATA));
+    reader.fill_buf().unwrap();
+    reader.consume(2);
+
+    // Both directions stay inside the buffer, so the inner reader is not
+    // touched.
+    reader.seek_relative(3).unwrap();
+    assert_eq!(reader.buffer(), &DATA[5..8]);
+    reader.seek_relative(-4).unwrap();
+    assert_eq!(reader.buffer(), &DATA[1..8]);
+    reader.seek_relative(7).unwrap();
+    assert!(reader.buffer().is_empty());
+    assert_eq!(reader.get_ref().position(), 8);
+    assert_eq!(reader.stream_position().unwrap(), 8);
+}
+
+#[test]
+fn seek_relative_outside_buffer() {
+    let mut reader = BufReader::<_, 8>::new_inline(Cursor::new(DATA));
+    reader.fill_buf().unwrap();
+    reader.consume(2);
+
+    reader.seek_relative(10).unwrap();
+    assert!(reader.buffer().is_empty());
+    assert_eq!(reader.get_ref().position(), 12);
+    assert_eq!(reader.fill_buf().unwrap(), &DATA[12..20]);
+
+    reader.seek_relative(-3).unwrap();
+    assert!(reader.buffer().is_empty());
+    assert_eq!(reader.get_ref().position(), 9);
+}
+
+#[test]
+fn seek_current_accounts_for_buffer() {
+    let mut reader = BufReader::<_, 8>::new_inline(Cursor::new(DATA));
+    reader.fill_buf().unwrap();
+    reader.consume(3);
+    assert_eq!(reader.get_ref().position(), 8);
+
+    // The position is relative to what the caller has read, not to the inner
+    // reader, which is 5 bytes ahead.
+    assert_eq!(reader.seek(SeekFrom::Current(2)).unwrap(), 5);
+    assert!(reader.buffer().is_empty());
+    assert_eq!(reader.fill_buf().unwrap(), &DATA[5..13]);
+
+    reader.consume(1);
+    assert_eq!(reader.seek(SeekFrom::Current(-6)).unwrap(), 0);
+    assert_eq!(reader.fill_buf().unwrap(), &DATA[..8]);
+
+    assert_eq!(reader.seek(SeekFrom::Start(30)).unwrap(), 30);
+    assert_eq!(reader.fill_buf().unwrap(), &DATA[30..38]);
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "alloc")]
+
+use axio::{BufWriter, Error, Write};
+
+mod common;
+
+use common::Mock;
+
+#[test]
+fn flushes_on_drop() {
+    let mut inner = Mock::new(b"", usize::MAX);
+    let mut writer = BufWriter::with_capacity(16, &mut inner);
+    writer.write_all(b"hello").unwrap();
+    assert!(writer.get_ref().writes.is_empty());
+
+    drop(writer);
+    assert_eq!(inner.data, b"hello");
+}
+
+#[test]
+fn into_inner_error_keeps_buffer() {
+    let mut inner = Mock::new(b"", usize::MAX);
+    let mut writer = BufWriter::with_capacity(16, &mut inner);
+    writer.write_all(b"pending").unwrap();
+
+    writer.get_mut().budget = 0;
+    writer.get_mut().fail = Error::BrokenPipe;
+    let err = writer.into_inner().unwrap_err();
+    assert_eq!(*err.error(), Error::BrokenPipe);
+
+    let writer = err.into_inner();
+    assert_eq!(writer.buffer(), b"pending");
+    let (_, buf) = writer.into_parts();
+    assert_eq!(buf, b"pending");
+    assert!(inner.writes.is_empty());
+}
+
+#[test]
+fn large_writes_bypass_buffer() {
+    let mut inner = Mock::new(b"", usize::MAX);
+    let mut writer = BufWriter::with_capacity(4, &mut inner);
+    writer.write_all(b"ab").unwrap();
+
+    // The buffered bytes go out first, then the large write in one piece.
+    writer.write_all(b"0123456789").unwrap();
+    assert!(writer.buffer().is_empty());
+    assert_eq!(writer.get_ref().writes, [2, 10]);
+
+    assert_eq!(writer.write(b"ABCDEFGH").unwrap(), 8);
+    assert!(writer.buffer().is_empty());
+    drop(writer);
+    assert_eq!(inner.writes, [2, 10, 8]);
+    assert_eq!(inner.data, b"ab0123456789ABCDEFGH");
+}
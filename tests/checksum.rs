@@ -0,0 +1,151 @@
+#![cfg(feature = "alloc")]
+
+use axio::{
+    Adler32, BufRead, BufReader, Checksum, ChecksumReader, ChecksumWriter, Crc32, Crc32c, Cursor,
+    Error, Read, Write,
+};
+
+mod common;
+
+use common::Mock;
+
+const CHECK: &[u8] = b"123456789";
+
+fn checksum<C: Checksum>(mut c: C, data: &[u8]) -> C::Output {
+    c.update(data);
+    c.finish()
+}
+
+/// Data larger than Adler-32's `NMAX` block, so that the sums wrap.
+fn long_data() -> Vec<u8> {
+    (0..20000u32).map(|i| (i * 31 + 7) as u8 | 0x80).collect()
+}
+
+#[test]
+fn known_answers() {
+    assert_eq!(checksum(Crc32::new(), CHECK), 0xcbf4_3926);
+    assert_eq!(checksum(Crc32c::new(), CHECK), 0xe306_9283);
+    assert_eq!(checksum(Adler32::new(), b"Wikipedia"), 0x11e6_0398);
+
+    assert_eq!(checksum(Crc32::new(), b""), 0);
+    assert_eq!(checksum(Crc32c::new(), b""), 0);
+    assert_eq!(checksum(Adler32::new(), b""), 1);
+}
+
+#[test]
+fn adler32_all_ones_wraps() {
+    // 0xff bytes make `b` grow fastest; the reference value comes from zlib.
+    assert_eq!(checksum(Adler32::new(), &[0xff; 5553]), 0x8e29_9c8b);
+}
+
+fn chunked_matches_single<C: Checksum<Output = u32> + Default>() {
+    let data = long_data();
+    let whole = checksum(C::default(), &data);
+    for size in [1, 7, 5552, 5553, 8191] {
+        let mut c = C::default();
+        for chunk in data.chunks(size) {
+            c.update(chunk);
+        }
+        assert_eq!(c.finish(), whole, "chunk size {size}");
+    }
+}
+
+#[test]
+fn chunked_updates_match() {
+    chunked_matches_single::<Crc32>();
+    chunked_matches_single::<Crc32c>();
+    chunked_matches_single::<Adler32>();
+}
+
+#[test]
+fn reset_and_verify() {
+    let mut c = Crc32::new();
+    c.update(b"garbage");
+    c.reset();
+    c.update(CHECK);
+    c.verify(0xcbf4_3926).unwrap();
+    assert_eq!(c.verify(0).unwrap_err(), Error::InvalidData);
+}
+
+#[test]
+fn writer_checksums_accepted_bytes() {
+    let mut out = [0; 4];
+    let mut writer = ChecksumWriter::new(&mut out[..], Crc32::new());
+    assert_eq!(writer.write(CHECK).unwrap(), 4);
+    assert_eq!(writer.checksum(), checksum(Crc32::new(), b"1234"));
+}
+
+#[test]
+fn reader_checksums_buffered_reads() {
+    let mut reader = ChecksumReader::new(Mock::new(CHECK, 4), Crc32::new());
+    let mut line = Vec::new();
+    reader.read_until(b'\n', &mut line).unwrap();
+    assert_eq!(line, CHECK);
+    reader.verify(0xcbf4_3926).unwrap();
+}
+
+#[test]
+fn reader_only_checksums_consumed_bytes() {
+    let mut reader = ChecksumReader::new(CHECK, Adler32::new());
+    assert_eq!(reader.fill_buf().unwrap(), CHECK);
+    reader.consume(3);
+    assert_eq!(reader.checksum().unwrap(), checksum(Adler32::new(), b"123"));
+
+    // Consuming again without `fill_buf` still finds the right bytes.
+    reader.consume(2);
+    assert_eq!(
+        reader.checksum().unwrap(),
+        checksum(Adler32::new(), b"12345")
+    );
+
+    let mut rest = [0; 4];
+    reader.read_exact(&mut rest).unwrap();
+    assert_eq!(reader.checksum().unwrap(), checksum(Adler32::new(), CHECK));
+}
+
+#[test]
+fn reader_moved_between_fill_and_consume() {
+    // The inner buffer lives inline, so it moves along with the reader.
+    let mut reader = ChecksumReader::new(Cursor::new(*b"abcdefgh"), Crc32::new());
+    assert_eq!(reader.fill_buf().unwrap(), b"abcdefgh");
+    let mut moved = reader;
+    moved.consume(3);
+    assert_eq!(moved.checksum().unwrap(), 0x3524_41c2);
+
+    let mut reader = ChecksumReader::new(
+        BufReader::<_, 4>::new_inline(&b"abcdefgh"[..]),
+        Crc32::new(),
+    );
+    assert_eq!(reader.fill_buf().unwrap(), b"abcd");
+    let mut moved = Box::new(reader);
+    moved.consume(4);
+    moved.consume(0);
+    assert_eq!(moved.checksum().unwrap(), checksum(Crc32::new(), b"abcd"));
+}
+
+#[test]
+fn reader_reports_failed_refetch() {
+    let mut reader = ChecksumReader::new(Mock::new(CHECK, 4), Crc32::new());
+    assert_eq!(reader.fill_buf().unwrap(), b"1234");
+    reader.consume(2);
+
+    // The bytes can no longer be fetched again to be checksummed.
+    reader.get_mut().blocked = true;
+    reader.consume(2);
+    assert_eq!(reader.checksum().unwrap_err(), Error::WouldBlock);
+    assert_eq!(reader.verify(0).unwrap_err(), Error::WouldBlock);
+
+    // They are consumed nonetheless, and the error sticks.
+    reader.get_mut().blocked = false;
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"56789");
+    assert_eq!(reader.checksum().unwrap_err(), Error::WouldBlock);
+}
+
+#[test]
+fn reader_reports_consume_past_buffer() {
+    let mut reader = ChecksumReader::new(Mock::new(CHECK, 4), Crc32::new());
+    reader.consume(6);
+    assert_eq!(reader.verify(0).unwrap_err(), Error::InvalidInput);
+}
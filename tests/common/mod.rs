@@ -0,0 +1,209 @@
+//! Test doubles shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axio::{
+    AsyncBufRead, AsyncRead, AsyncWrite, BufRead, Error, IoEvents, Pollable, Read, ReadAt, Result,
+    Write,
+};
+
+/// A reader and writer with configurable short transfers and failures.
+///
+/// Reads take bytes from the front of `data` and writes append to it. Every
+/// call moves at most `limit` bytes, and the length of each successful write
+/// is recorded in `writes`.
+///
+/// While `blocked` is set every call fails with [`Error::WouldBlock`]. Once
+/// `budget` bytes have been moved every call fails with `fail`; for
+/// positioned reads, `budget` is the offset from which reads fail instead.
+///
+/// With `stutter` set, the asynchronous traits are pending on the first poll
+/// and every other poll after it.
+#[derive(Debug, Clone)]
+pub struct Mock {
+    pub data: Vec<u8>,
+    pub limit: usize,
+    pub budget: usize,
+    pub fail: Error,
+    pub blocked: bool,
+    pub stutter: bool,
+    pub writes: Vec<usize>,
+    ready: bool,
+}
+
+impl Mock {
+    /// Creates a mock over `data` moving at most `limit` bytes per call.
+    pub fn new(data: &[u8], limit: usize) -> Mock {
+        Mock {
+            data: data.to_vec(),
+            limit,
+            budget: usize::MAX,
+            fail: Error::Io,
+            blocked: false,
+            stutter: false,
+            writes: Vec::new(),
+            ready: true,
+        }
+    }
+
+    /// Creates a mock that stops moving bytes after `budget` of them, then
+    /// fails with `fail`.
+    pub fn failing(data: &[u8], limit: usize, budget: usize, fail: Error) -> Mock {
+        Mock {
+            budget,
+            fail,
+            ..Mock::new(data, limit)
+        }
+    }
+
+    /// Creates a mock whose asynchronous calls are pending every other poll.
+    pub fn stutter(data: &[u8], limit: usize) -> Mock {
+        Mock {
+            stutter: true,
+            ..Mock::new(data, limit)
+        }
+    }
+
+    fn check(&self) -> Result {
+        if self.blocked {
+            Err(Error::WouldBlock)
+        } else if self.budget == 0 {
+            Err(self.fail)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn available(&self) -> usize {
+        self.data.len().min(self.limit).min(self.budget)
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if !self.stutter {
+            return Poll::Ready(());
+        }
+        self.ready = !self.ready;
+        if self.ready {
+            Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+impl Read for Mock {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = {
+            let data = self.fill_buf()?;
+            let n = data.len().min(buf.len());
+            buf[..n].copy_from_slice(&data[..n]);
+            n
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for Mock {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.check()?;
+        Ok(&self.data[..self.available()])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.data.drain(..amt);
+        self.budget = self.budget.saturating_sub(amt);
+    }
+}
+
+impl Write for Mock {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.check()?;
+        let n = buf.len().min(self.limit).min(self.budget);
+        self.data.extend_from_slice(&buf[..n]);
+        self.budget -= n;
+        self.writes.push(n);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result {
+        Ok(())
+    }
+}
+
+impl ReadAt for Mock {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize> {
+        if self.blocked {
+            return Err(Error::WouldBlock);
+        }
+        if offset >= self.budget as u64 {
+            return Err(self.fail);
+        }
+        let data = self.data.get(offset as usize..).unwrap_or_default();
+        let n = data.len().min(self.limit).min(buf.len());
+        buf[..n].copy_from_slice(&data[..n]);
+        Ok(n)
+    }
+}
+
+/// Readable while there is unread data and the mock is not blocked; always
+/// writable.
+impl Pollable for Mock {
+    fn poll(&self) -> IoEvents {
+        let mut events = IoEvents::OUT | IoEvents::WRNORM;
+        if !self.blocked && !self.data.is_empty() {
+            events |= IoEvents::IN | IoEvents::RDNORM;
+        }
+        events
+    }
+
+    fn register(&self, _context: &mut Context<'_>, _events: IoEvents) {}
+}
+
+impl AsyncRead for Mock {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+        if this.poll_ready(cx).is_pending() {
+            return Poll::Pending;
+        }
+        Poll::Ready(this.read(buf))
+    }
+}
+
+impl AsyncBufRead for Mock {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<&[u8]>> {
+        let this = self.get_mut();
+        if this.poll_ready(cx).is_pending() {
+            return Poll::Pending;
+        }
+        Poll::Ready(this.fill_buf())
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        BufRead::consume(self.get_mut(), amt)
+    }
+}
+
+impl AsyncWrite for Mock {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+        if this.poll_ready(cx).is_pending() {
+            return Poll::Pending;
+        }
+        Poll::Ready(this.write(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "alloc")]
+
+use axio::{copy, copy_buf, copy_with_buf, BufReader, Error};
+
+mod common;
+
+use common::Mock;
+
+fn data() -> Vec<u8> {
+    (0..5000).map(|i| i as u8).collect()
+}
+
+#[test]
+fn copy_retries_short_writes() {
+    let data = data();
+    let mut writer = Mock::new(b"", 7);
+    assert_eq!(copy(&mut &data[..], &mut writer).unwrap(), 5000);
+    assert_eq!(writer.data, data);
+    assert!(writer.writes.len() >= 5000 / 7);
+}
+
+#[test]
+fn copy_buf_retries_short_writes() {
+    let data = data();
+    let mut reader = BufReader::new(&data[..]);
+    let mut writer = Mock::new(b"", 7);
+    assert_eq!(copy_buf(&mut reader, &mut writer).unwrap(), 5000);
+    assert_eq!(writer.data, data);
+}
+
+#[test]
+fn copy_fails_on_zero_write() {
+    let mut writer = Mock::new(b"", 0);
+    assert_eq!(copy(&mut &b"abc"[..], &mut writer).unwrap_err(), Error::Io);
+    assert_eq!(
+        copy_buf(&mut &b"abc"[..], &mut writer).unwrap_err(),
+        Error::Io
+    );
+}
+
+#[test]
+fn copy_with_buf_small_buffer() {
+    let data = data();
+    let mut writer = Mock::new(b"", 5);
+    assert_eq!(
+        copy_with_buf(&mut &data[..], &mut writer, &mut [0; 3]).unwrap(),
+        5000
+    );
+    assert_eq!(writer.data, data);
+}
+
+#[test]
+fn copy_with_buf_rejects_empty_buffer() {
+    let mut writer = Vec::new();
+    assert_eq!(
+        copy_with_buf(&mut &b"abc"[..], &mut writer, &mut []).unwrap_err(),
+        Error::InvalidInput
+    );
+    assert!(writer.is_empty());
+}
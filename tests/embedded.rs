@@ -0,0 +1,169 @@
+#![cfg(all(feature = "alloc", feature = "embedded-io"))]
+
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+    task::{Context, Poll, Waker},
+};
+
+use axio::{
+    async_io::{AsyncReadExt, AsyncWriteExt},
+    from_embedded_error_kind, to_embedded_error_kind, AsyncBufRead, EmbeddedError, Error,
+    FromEmbedded, FromEmbeddedAsync, Read, ToEmbedded,
+};
+use embedded_io::{Error as _, ErrorKind};
+use embedded_io_async::BufRead as _;
+
+mod common;
+
+use common::Mock;
+
+/// Polls `fut` with a no-op waker until it completes, returning the result
+/// and how many times it was pending.
+fn block_on<F: Future>(fut: F) -> (F::Output, usize) {
+    let mut fut = pin!(fut);
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut pending = 0;
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(out) => return (out, pending),
+            Poll::Pending => pending += 1,
+        }
+    }
+}
+
+#[test]
+fn error_kinds() {
+    for (e, kind) in [
+        (Error::BrokenPipe, ErrorKind::BrokenPipe),
+        (Error::InvalidData, ErrorKind::InvalidData),
+        (Error::NoMemory, ErrorKind::OutOfMemory),
+        (Error::TimedOut, ErrorKind::TimedOut),
+    ] {
+        assert_eq!(to_embedded_error_kind(e), kind);
+        assert_eq!(from_embedded_error_kind(kind), e);
+        assert_eq!(EmbeddedError(e).kind(), kind);
+    }
+
+    // Errors and kinds with no counterpart.
+    assert_eq!(to_embedded_error_kind(Error::WouldBlock), ErrorKind::Other);
+    assert_eq!(to_embedded_error_kind(Error::BadState), ErrorKind::Other);
+    assert_eq!(from_embedded_error_kind(ErrorKind::Other), Error::Io);
+    assert_eq!(
+        from_embedded_error_kind(ErrorKind::ConnectionAborted),
+        Error::ConnectionReset
+    );
+}
+
+#[test]
+fn errors_pass_through_to_embedded() {
+    let mut reader = ToEmbedded::new(axio::empty());
+    let mut buf = [0; 1];
+    assert_eq!(
+        embedded_io::Read::read_exact(&mut reader, &mut buf).unwrap_err(),
+        embedded_io::ReadExactError::UnexpectedEof
+    );
+
+    let (mut pipe_reader, _writer) = axio::pipe(4).unwrap();
+    let mut reader = ToEmbedded::new(&mut pipe_reader);
+    let err = embedded_io::Read::read(&mut reader, &mut buf).unwrap_err();
+    assert_eq!(err, EmbeddedError(Error::WouldBlock));
+}
+
+#[test]
+fn from_embedded_read_exact() {
+    let mut reader = FromEmbedded::new(&b"abc"[..]);
+    let mut buf = [0; 2];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"ab");
+    assert_eq!(reader.read_exact(&mut buf).unwrap_err(), Error::Io);
+}
+
+#[test]
+fn async_fill_buf_across_pending() {
+    let mut reader = ToEmbedded::new(Mock::stutter(b"hello", usize::MAX));
+
+    let (res, pending) = block_on(async {
+        let buf = reader.fill_buf().await?;
+        assert_eq!(buf, b"hello");
+        let len = buf.len();
+        reader.consume(2);
+        reader.fill_buf().await.map(|buf| (buf.to_vec(), len))
+    });
+    assert_eq!(res.unwrap(), (b"llo".to_vec(), 5));
+    assert_eq!(pending, 2);
+
+    reader.get_mut().data.clear();
+    let (res, _) = block_on(reader.fill_buf());
+    assert!(res.unwrap().is_empty());
+}
+
+#[test]
+fn from_embedded_async_read() {
+    let inner = ToEmbedded::new(Mock::stutter(b"hello world", 4));
+    let mut reader = FromEmbeddedAsync::with_capacity(3, inner);
+
+    let mut buf = Vec::new();
+    let (res, pending) = block_on(reader.read_to_end(&mut buf));
+    assert_eq!(res.unwrap(), 11);
+    assert_eq!(buf, b"hello world");
+    assert!(pending > 0);
+    assert!(reader.get_ref().is_some());
+}
+
+#[test]
+fn from_embedded_async_fill_buf() {
+    let inner = ToEmbedded::new(Mock::stutter(b"abcdef", 4));
+    let mut reader = FromEmbeddedAsync::new(inner);
+
+    let (res, _) = block_on(std::future::poll_fn(|cx| {
+        Pin::new(&mut reader)
+            .poll_fill_buf(cx)
+            .map_ok(<[u8]>::to_vec)
+    }));
+    assert_eq!(res.unwrap(), b"abcd");
+    Pin::new(&mut reader).consume(3);
+    assert_eq!(reader.buffer(), b"d");
+
+    // Reading drains the buffer before the object is read again.
+    let mut buf = [0; 8];
+    let (res, _) = block_on(reader.read(&mut buf));
+    assert_eq!(res.unwrap(), 1);
+    assert_eq!(buf[0], b'd');
+    let (res, _) = block_on(reader.read(&mut buf));
+    assert_eq!(&buf[..res.unwrap()], b"ef");
+    let (res, _) = block_on(reader.read(&mut buf));
+    assert_eq!(res.unwrap(), 0);
+}
+
+#[test]
+fn from_embedded_async_write() {
+    let inner = ToEmbedded::new(Mock::stutter(b"", 3));
+    let mut writer = FromEmbeddedAsync::with_capacity(4, inner);
+
+    let (res, _) = block_on(async {
+        writer.write_all(b"hello world").await?;
+        writer.flush().await
+    });
+    res.unwrap();
+    let mock = writer.into_inner().unwrap().into_inner();
+    assert_eq!(mock.data, b"hello world");
+    assert_eq!(mock.writes, [3, 1, 3, 1, 3]);
+}
+
+#[test]
+fn from_embedded_async_write_error_reported_later() {
+    let mut mock = Mock::failing(b"", 2, 3, Error::BrokenPipe);
+    mock.stutter = true;
+    let mut writer = FromEmbeddedAsync::new(ToEmbedded::new(mock));
+
+    // The data is accepted before it reaches the object...
+    let (res, _) = block_on(writer.write(b"abcdef"));
+    assert_eq!(res.unwrap(), 6);
+    assert!(writer.get_ref().is_none());
+
+    // ...and failing to write it out fails the flush.
+    let (res, _) = block_on(writer.flush());
+    assert_eq!(res.unwrap_err(), Error::BrokenPipe);
+    assert_eq!(writer.into_inner().unwrap().into_inner().data, b"abc");
+}
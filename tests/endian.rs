@@ -0,0 +1,85 @@
+#![cfg(feature = "alloc")]
+
+use axio::{BufReader, Error, ReadBytesExt, WriteBytesExt};
+
+mod common;
+
+use common::Mock;
+
+/// Writes each value in both byte orders, checks the encoding against the
+/// standard library and reads it back, through short reads and writes.
+macro_rules! round_trip {
+    ($($ty:ident: $read_le:ident, $read_be:ident, $write_le:ident, $write_be:ident;)*) => {$(
+        for n in [$ty::MIN, $ty::MAX, 0 as $ty, 1 as $ty, 0x5a as $ty] {
+            let mut writer = Mock::new(b"", 3);
+            writer.$write_le(n).unwrap();
+            writer.$write_be(n).unwrap();
+            let mut expected = n.to_le_bytes().to_vec();
+            expected.extend_from_slice(&n.to_be_bytes());
+            assert_eq!(writer.data, expected, "{}", stringify!($ty));
+
+            let mut reader = Mock::new(&writer.data, 3);
+            assert_eq!(reader.$read_le().unwrap(), n);
+            assert_eq!(reader.$read_be().unwrap(), n);
+
+            let mut reader = BufReader::<_, 5>::new_inline(&writer.data[..]);
+            assert_eq!(reader.$read_le().unwrap(), n);
+            assert_eq!(reader.$read_be().unwrap(), n);
+        }
+    )*};
+}
+
+#[test]
+fn fixed_width_round_trip() {
+    round_trip! {
+        u16: read_u16_le, read_u16_be, write_u16_le, write_u16_be;
+        u32: read_u32_le, read_u32_be, write_u32_le, write_u32_be;
+        u64: read_u64_le, read_u64_be, write_u64_le, write_u64_be;
+        u128: read_u128_le, read_u128_be, write_u128_le, write_u128_be;
+        i16: read_i16_le, read_i16_be, write_i16_le, write_i16_be;
+        i32: read_i32_le, read_i32_be, write_i32_le, write_i32_be;
+        i64: read_i64_le, read_i64_be, write_i64_le, write_i64_be;
+        i128: read_i128_le, read_i128_be, write_i128_le, write_i128_be;
+        f32: read_f32_le, read_f32_be, write_f32_le, write_f32_be;
+        f64: read_f64_le, read_f64_be, write_f64_le, write_f64_be;
+    }
+}
+
+#[test]
+fn known_encodings() {
+    let mut buf = Vec::new();
+    buf.write_u8(0xab).unwrap();
+    buf.write_i8(-2).unwrap();
+    buf.write_u32_le(0x0102_0304).unwrap();
+    buf.write_i16_be(-2).unwrap();
+    buf.write_f32_be(1.0).unwrap();
+    assert_eq!(buf, [0xab, 0xfe, 4, 3, 2, 1, 0xff, 0xfe, 0x3f, 0x80, 0, 0]);
+
+    let mut reader = &buf[..];
+    assert_eq!(reader.read_u8().unwrap(), 0xab);
+    assert_eq!(reader.read_i8().unwrap(), -2);
+    assert_eq!(reader.read_u32_le().unwrap(), 0x0102_0304);
+    assert_eq!(reader.read_i16_be().unwrap(), -2);
+    assert_eq!(reader.read_f32_be().unwrap(), 1.0);
+}
+
+#[test]
+fn short_input_is_eof() {
+    let mut reader = Mock::new(&[1, 2, 3], 1);
+    assert_eq!(reader.read_u32_le().unwrap_err(), Error::Io);
+    assert_eq!((&[1u8; 15][..]).read_u128_be().unwrap_err(), Error::Io);
+    assert_eq!((&[][..]).read_u8().unwrap_err(), Error::Io);
+    assert_eq!((&[0u8; 7][..]).read_f64_le().unwrap_err(), Error::Io);
+
+    let mut reader = BufReader::<_, 4>::new_inline(&[1, 2, 3, 4, 5][..]);
+    assert_eq!(reader.read_u32_be().unwrap(), 0x0102_0304);
+    assert_eq!(reader.read_u16_le().unwrap_err(), Error::Io);
+}
+
+#[test]
+fn write_fails_on_short_sink() {
+    let mut out = [0; 3];
+    let mut writer = &mut out[..];
+    assert_eq!(writer.write_u32_be(0x0102_0304).unwrap_err(), Error::Io);
+    assert_eq!(out, [1, 2, 3]);
+}
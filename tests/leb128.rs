@@ -0,0 +1,119 @@
+#![cfg(feature = "alloc")]
+
+use axio::{BufReadBytesExt, BufReader, Error, ReadBytesExt, WriteBytesExt};
+
+fn uleb128(n: u64) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.write_uleb128(n).unwrap();
+    buf
+}
+
+fn sleb128(n: i64) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.write_sleb128(n).unwrap();
+    buf
+}
+
+#[test]
+fn spec_vectors() {
+    assert_eq!(uleb128(624485), [0xe5, 0x8e, 0x26]);
+    assert_eq!((&[0xe5, 0x8e, 0x26][..]).read_uleb128().unwrap(), 624485);
+
+    assert_eq!(sleb128(-123456), [0xc0, 0xbb, 0x78]);
+    assert_eq!((&[0xc0, 0xbb, 0x78][..]).read_sleb128().unwrap(), -123456);
+}
+
+#[test]
+fn ten_byte_encodings() {
+    let max = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+    assert_eq!(uleb128(u64::MAX), max);
+    assert_eq!((&max[..]).read_uleb128().unwrap(), u64::MAX);
+
+    let min = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f];
+    assert_eq!(sleb128(i64::MIN), min);
+    assert_eq!((&min[..]).read_sleb128().unwrap(), i64::MIN);
+
+    let max = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+    assert_eq!(sleb128(i64::MAX), max);
+    assert_eq!((&max[..]).read_sleb128().unwrap(), i64::MAX);
+}
+
+#[test]
+fn invalid_tenth_byte() {
+    let mut bytes = [0xff; 10];
+    bytes[9] = 0x02;
+    assert_eq!((&bytes[..]).read_uleb128().unwrap_err(), Error::InvalidData);
+    assert_eq!((&bytes[..]).read_sleb128().unwrap_err(), Error::InvalidData);
+    assert_eq!(
+        (&bytes[..]).read_uleb128_buffered().unwrap_err(),
+        Error::InvalidData
+    );
+
+    // A continuation bit on the 10th byte is just as invalid.
+    bytes[9] = 0x81;
+    assert_eq!((&bytes[..]).read_uleb128().unwrap_err(), Error::InvalidData);
+}
+
+#[test]
+fn zigzag_vectors() {
+    for (n, encoded) in [(0, 0), (-1, 1), (1, 2), (-2, 3), (i64::MAX, u64::MAX - 1)] {
+        let mut buf = Vec::new();
+        buf.write_zigzag(n).unwrap();
+        assert_eq!(buf, uleb128(encoded));
+        assert_eq!((&buf[..]).read_zigzag().unwrap(), n);
+    }
+    let mut buf = Vec::new();
+    buf.write_zigzag(i64::MIN).unwrap();
+    assert_eq!(buf, uleb128(u64::MAX));
+}
+
+#[test]
+fn buffered_matches_unbuffered() {
+    let unsigned = [0, 1, 127, 128, 300, 624485, u32::MAX as u64, u64::MAX];
+    let signed = [0, -1, 63, -64, 64, -65, -123456, i64::MIN, i64::MAX];
+
+    let mut data = Vec::new();
+    for &n in &unsigned {
+        data.write_uleb128(n).unwrap();
+    }
+    for &n in &signed {
+        data.write_sleb128(n).unwrap();
+        data.write_zigzag(n).unwrap();
+    }
+    // A truncated value at the end.
+    data.push(0x80);
+
+    // A tiny buffer makes values straddle refills.
+    let mut plain = &data[..];
+    let mut buffered = BufReader::<_, 3>::new_inline(&data[..]);
+    for &n in &unsigned {
+        assert_eq!(plain.read_uleb128().unwrap(), n);
+        assert_eq!(buffered.read_uleb128_buffered().unwrap(), n);
+    }
+    for &n in &signed {
+        assert_eq!(plain.read_sleb128().unwrap(), n);
+        assert_eq!(buffered.read_sleb128_buffered().unwrap(), n);
+        assert_eq!(plain.read_zigzag().unwrap(), n);
+        assert_eq!(buffered.read_zigzag_buffered().unwrap(), n);
+    }
+    assert_eq!(plain.read_uleb128().unwrap_err(), Error::Io);
+    assert_eq!(buffered.read_uleb128_buffered().unwrap_err(), Error::Io);
+}
+
+#[test]
+fn buffered_consumes_like_unbuffered_on_error() {
+    let mut bytes = vec![0xff; 9];
+    bytes.extend_from_slice(&[0x02, 0x2a]);
+
+    let mut plain = &bytes[..];
+    let mut buffered = BufReader::<_, 4>::new_inline(&bytes[..]);
+    assert_eq!(plain.read_uleb128().unwrap_err(), Error::InvalidData);
+    assert_eq!(
+        buffered.read_uleb128_buffered().unwrap_err(),
+        Error::InvalidData
+    );
+
+    // Both stop right after the offending byte.
+    assert_eq!(plain.read_uleb128().unwrap(), 42);
+    assert_eq!(buffered.read_uleb128_buffered().unwrap(), 42);
+}
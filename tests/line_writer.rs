@@ -0,0 +1,58 @@
+#![cfg(feature = "alloc")]
+
+use axio::{Error, LineWriter, Write};
+
+mod common;
+
+use common::Mock;
+
+#[test]
+fn flushes_up_to_last_newline() {
+    let mut writer = LineWriter::with_capacity(16, Mock::new(b"", usize::MAX));
+    assert_eq!(writer.write(b"a\nb\nc").unwrap(), 5);
+    assert_eq!(writer.get_ref().data, b"a\nb\n");
+    assert_eq!(writer.buffer(), b"c");
+}
+
+#[test]
+fn keeps_tail_buffered() {
+    let mut writer = LineWriter::with_capacity(16, Mock::new(b"", usize::MAX));
+    writer.write_all(b"one\ntw").unwrap();
+    writer.write_all(b"o").unwrap();
+    assert_eq!(writer.get_ref().data, b"one\n");
+    assert_eq!(writer.buffer(), b"two");
+
+    writer.write_all(b"\nthree").unwrap();
+    assert_eq!(writer.get_ref().data, b"one\ntwo\n");
+    assert_eq!(writer.buffer(), b"three");
+}
+
+#[test]
+fn short_inner_write_mid_line() {
+    let mut writer = LineWriter::with_capacity(16, Mock::new(b"", 2));
+    // Only "ab" reaches the inner writer; the rest of the line is buffered
+    // and counted, the partial line after it is not.
+    assert_eq!(writer.write(b"abc\ndef").unwrap(), 4);
+    assert_eq!(writer.get_ref().data, b"ab");
+    assert_eq!(writer.buffer(), b"c\n");
+
+    writer.write_all(b"def").unwrap();
+    assert_eq!(writer.get_ref().data, b"abc\n");
+    assert_eq!(writer.buffer(), b"def");
+}
+
+#[test]
+fn would_block_keeps_buffer() {
+    let mut writer = LineWriter::with_capacity(16, Mock::new(b"", usize::MAX));
+    writer.write_all(b"abc").unwrap();
+
+    writer.get_mut().blocked = true;
+    assert_eq!(writer.write(b"x\n").unwrap_err(), Error::WouldBlock);
+    assert_eq!(writer.buffer(), b"abc");
+    assert!(writer.get_ref().data.is_empty());
+
+    writer.get_mut().blocked = false;
+    writer.write_all(b"x\n").unwrap();
+    assert_eq!(writer.get_ref().data, b"abcx\n");
+    assert!(writer.buffer().is_empty());
+}
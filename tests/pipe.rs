@@ -0,0 +1,123 @@
+#![cfg(feature = "alloc")]
+
+use axio::{pipe, pipe_with_pipe_buf, BufRead, Error, Read, Write};
+
+#[test]
+fn atomic_write_would_block() {
+    let (mut reader, mut writer) = pipe_with_pipe_buf(8, 4).unwrap();
+    assert_eq!(writer.write(b"abcdef").unwrap(), 6);
+
+    // Only two bytes are free, and a write of up to `pipe_buf` bytes must not
+    // be split.
+    assert_eq!(writer.write(b"xyz").unwrap_err(), Error::WouldBlock);
+    assert_eq!(reader.len(), 6);
+
+    assert_eq!(writer.write(b"gh").unwrap(), 2);
+    let mut buf = [0; 8];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"abcdefgh");
+}
+
+#[test]
+fn large_write_is_split() {
+    let (mut reader, mut writer) = pipe_with_pipe_buf(8, 4).unwrap();
+    let data = b"0123456789ab";
+    assert_eq!(writer.write(data).unwrap(), 8);
+    assert_eq!(writer.write(data).unwrap_err(), Error::WouldBlock);
+
+    let mut buf = [0; 3];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"012");
+    // A write larger than `pipe_buf` takes whatever space is free.
+    assert_eq!(writer.write(b"89abcd").unwrap(), 3);
+    assert_eq!(writer.free(), 0);
+
+    let mut rest = Vec::new();
+    drop(writer);
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"3456789a");
+}
+
+#[test]
+fn broken_pipe_after_reader_dropped() {
+    let (reader, mut writer) = pipe(8).unwrap();
+    writer.write_all(b"abc").unwrap();
+    drop(reader);
+    assert!(writer.is_closed());
+    assert_eq!(writer.write(b"d").unwrap_err(), Error::BrokenPipe);
+    assert_eq!(writer.write(b"").unwrap_err(), Error::BrokenPipe);
+}
+
+#[test]
+fn eof_after_writer_dropped() {
+    let (mut reader, mut writer) = pipe(8).unwrap();
+    let mut buf = [0; 8];
+    assert_eq!(reader.read(&mut buf).unwrap_err(), Error::WouldBlock);
+
+    writer.write_all(b"ab").unwrap();
+    drop(writer);
+    assert!(reader.is_closed());
+
+    // Data written before the writer went away is still delivered.
+    assert_eq!(reader.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"ab");
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    assert_eq!(reader.fill_buf().unwrap(), b"");
+}
+
+#[test]
+fn fill_buf_stops_at_ring_end() {
+    let (mut reader, mut writer) = pipe(5).unwrap();
+    writer.write_all(b"abcd").unwrap();
+    reader.consume(3);
+    writer.write_all(b"efg").unwrap();
+
+    // "defg" straddles the end of the buffer and comes in two pieces.
+    assert_eq!(reader.fill_buf().unwrap(), b"de");
+    reader.consume(2);
+    assert_eq!(reader.fill_buf().unwrap(), b"fg");
+    reader.consume(2);
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn wraps_around_many_times() {
+    for capacity in [1, 3, 7, 1000] {
+        let (mut reader, mut writer) = pipe_with_pipe_buf(capacity, 1).unwrap();
+        let mut next = 0u8;
+        let mut expected = 0u8;
+        let mut total = 0;
+
+        // Move ten times the capacity through the pipe with uneven chunk
+        // sizes, so that the positions wrap at every possible offset.
+        let mut step = 1;
+        while total < 10 * capacity {
+            let chunk: Vec<u8> = (0..step)
+                .map(|_| {
+                    next = next.wrapping_add(1);
+                    next
+                })
+                .collect();
+            let mut written = 0;
+            while written < chunk.len() {
+                written += writer.write(&chunk[written..]).unwrap();
+                let mut buf = [0; 5];
+                let n = reader.read(&mut buf).unwrap();
+                for &byte in &buf[..n] {
+                    expected = expected.wrapping_add(1);
+                    assert_eq!(byte, expected, "capacity {capacity}");
+                }
+                total += n;
+            }
+            step = step % 11 + 1;
+        }
+        drop(writer);
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        for byte in rest {
+            expected = expected.wrapping_add(1);
+            assert_eq!(byte, expected, "capacity {capacity}");
+        }
+        assert_eq!(expected, next);
+    }
+}
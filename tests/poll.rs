@@ -0,0 +1,36 @@
+use axio::{BufRead, BufReader, IoEvents, Pollable};
+
+mod common;
+
+use common::Mock;
+
+#[test]
+fn bits_round_trip() {
+    for bits in [0, 0x1, 0x45, 0x2000, 0x3fff, 0x0800, 0x8000_0000, u32::MAX] {
+        assert_eq!(IoEvents::from_bits_retain(bits).bits(), bits);
+    }
+    // Unknown bits are kept alongside known ones.
+    let events = IoEvents::from_bits_retain(0x1000 | IoEvents::IN.bits());
+    assert!(events.contains(IoEvents::IN));
+    assert_eq!(events.bits(), 0x1001);
+    assert_eq!(IoEvents::from_bits(0x1000), None);
+}
+
+#[test]
+fn buf_reader_readable_while_buffered() {
+    let mut reader = BufReader::<_, 4>::new_inline(Mock::new(b"abcdef", 4));
+    assert!(reader.poll().contains(IoEvents::IN | IoEvents::RDNORM));
+
+    reader.fill_buf().unwrap();
+    reader.consume(1);
+    reader.get_mut().blocked = true;
+
+    // The inner reader is not readable, but three bytes are still buffered.
+    assert!(!reader.get_ref().poll().contains(IoEvents::IN));
+    let events = reader.poll();
+    assert!(events.contains(IoEvents::IN | IoEvents::RDNORM));
+    assert!(events.contains(IoEvents::OUT));
+
+    reader.consume(3);
+    assert!(!reader.poll().intersects(IoEvents::IN | IoEvents::RDNORM));
+}
@@ -0,0 +1,35 @@
+use axio::{Error, IoSliceMut, Read, ReadAtCursor};
+
+mod common;
+
+use common::Mock;
+
+#[test]
+fn read_vectored_keeps_partial_count() {
+    let mut cursor = ReadAtCursor::new(Mock::failing(b"abcdefgh", usize::MAX, 3, Error::Io));
+    let (mut a, mut b) = ([0; 3], [0; 3]);
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(cursor.read_vectored(&mut bufs).unwrap(), 3);
+    assert_eq!(&a, b"abc");
+    assert_eq!(cursor.position(), 3);
+
+    // Nothing can be read now, so the error is returned.
+    let mut bufs = [IoSliceMut::new(&mut b)];
+    assert_eq!(cursor.read_vectored(&mut bufs).unwrap_err(), Error::Io);
+    assert_eq!(cursor.position(), 3);
+}
+
+#[test]
+fn read_vectored_stops_at_short_read() {
+    let mut cursor = ReadAtCursor::new(&b"abcd"[..]);
+    let (mut a, mut b, mut c) = ([0; 3], [0; 3], [0; 3]);
+    let mut bufs = [
+        IoSliceMut::new(&mut a),
+        IoSliceMut::new(&mut b),
+        IoSliceMut::new(&mut c),
+    ];
+    assert_eq!(cursor.read_vectored(&mut bufs).unwrap(), 4);
+    assert_eq!(&a, b"abc");
+    assert_eq!(b[0], b'd');
+    assert_eq!(cursor.position(), 4);
+}
@@ -0,0 +1,94 @@
+#![feature(core_io_borrowed_buf)]
+
+use std::mem::MaybeUninit;
+
+use axio::{BorrowedBuf, BufReader, Error, Read};
+
+mod common;
+
+use common::Mock;
+
+/// Returns storage whose first `init` bytes are initialized to `0xff`.
+fn partially_init<const N: usize>(init: usize) -> [MaybeUninit<u8>; N] {
+    let mut storage = [MaybeUninit::uninit(); N];
+    for byte in &mut storage[..init] {
+        byte.write(0xff);
+    }
+    storage
+}
+
+#[test]
+fn read_buf_partially_initialized() {
+    let mut storage = partially_init::<16>(4);
+    let mut buf = BorrowedBuf::from(&mut storage[..]);
+    unsafe { buf.set_init(4) };
+
+    // `&[u8]` copies straight into the uninitialized part.
+    let mut reader = &b"0123456789"[..];
+    reader.read_buf(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"0123456789");
+    assert!(buf.init_len() >= 10);
+
+    // The default implementation initializes the rest before reading.
+    let mut reader = Mock::new(b"abc", 2);
+    reader.read_buf(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"0123456789ab");
+    assert_eq!(buf.init_len(), 16);
+}
+
+#[test]
+fn read_buf_exact_eof() {
+    let mut storage = partially_init::<8>(2);
+    let mut buf = BorrowedBuf::from(&mut storage[..]);
+    unsafe { buf.set_init(2) };
+
+    let mut reader = Mock::new(b"abcde", 2);
+    assert_eq!(
+        reader.read_buf_exact(buf.unfilled()).unwrap_err(),
+        Error::Io
+    );
+    // The bytes read before EOF stay in the buffer.
+    assert_eq!(buf.filled(), b"abcde");
+
+    let mut reader = &b"xyz"[..];
+    buf.clear();
+    assert_eq!(
+        reader.read_buf_exact(buf.unfilled()).unwrap_err(),
+        Error::Io
+    );
+    assert_eq!(buf.filled(), b"xyz");
+}
+
+#[test]
+fn buf_reader_read_buf() {
+    let mut reader = BufReader::<_, 4>::new_inline(Mock::new(b"0123456789abcdef", 8));
+
+    // A cursor at least as large as the buffer bypasses it: one inner read
+    // fills 8 bytes and nothing is buffered.
+    let mut storage = partially_init::<8>(0);
+    let mut buf = BorrowedBuf::from(&mut storage[..]);
+    reader.read_buf(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"01234567");
+    assert!(reader.buffer().is_empty());
+
+    // A smaller one goes through the buffer.
+    let mut storage = partially_init::<3>(0);
+    let mut buf = BorrowedBuf::from(&mut storage[..]);
+    reader.read_buf(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"89a");
+    assert_eq!(reader.buffer(), b"b");
+
+    // `read_buf_exact` drains the buffer first, then reads the rest.
+    let mut storage = partially_init::<4>(1);
+    let mut buf = BorrowedBuf::from(&mut storage[..]);
+    unsafe { buf.set_init(1) };
+    reader.read_buf_exact(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"bcde");
+
+    buf.clear();
+    assert_eq!(
+        reader.read_buf_exact(buf.unfilled()).unwrap_err(),
+        Error::Io
+    );
+    assert_eq!(buf.filled(), b"f");
+}
@@ -0,0 +1,96 @@
+use axio::{Cursor, Error, Result, Seek, SeekFrom};
+
+/// A seekable object that only implements [`Seek::seek`], to exercise the
+/// provided methods, and counts the seeks made.
+struct SeekOnly {
+    inner: Cursor<&'static [u8]>,
+    seeks: usize,
+}
+
+impl Seek for SeekOnly {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.seeks += 1;
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn resolve_rejects_negative_positions() {
+    assert_eq!(
+        SeekFrom::Current(-1).resolve(0, 10),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(SeekFrom::End(-11).resolve(5, 10), Err(Error::InvalidInput));
+    assert_eq!(
+        SeekFrom::End(i64::MIN).resolve(0, 0),
+        Err(Error::InvalidInput)
+    );
+
+    assert_eq!(SeekFrom::Current(-1).resolve(1, 10), Ok(0));
+    assert_eq!(SeekFrom::End(-10).resolve(5, 10), Ok(0));
+}
+
+#[test]
+fn resolve_rejects_overflow() {
+    let near_max = u64::MAX - 1;
+    assert_eq!(
+        SeekFrom::Current(i64::MAX).resolve(near_max, 0),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(
+        SeekFrom::End(i64::MAX).resolve(0, near_max),
+        Err(Error::InvalidInput)
+    );
+
+    assert_eq!(SeekFrom::Current(1).resolve(near_max, 0), Ok(u64::MAX));
+    assert_eq!(SeekFrom::Start(u64::MAX).resolve(1, 0), Ok(u64::MAX));
+}
+
+#[test]
+fn failed_seek_keeps_position() {
+    let mut cursor = Cursor::new(&b"hello"[..]);
+    cursor.seek(SeekFrom::Start(2)).unwrap();
+    assert_eq!(cursor.seek(SeekFrom::Current(-3)), Err(Error::InvalidInput));
+    assert_eq!(cursor.seek(SeekFrom::End(-6)), Err(Error::InvalidInput));
+    assert_eq!(cursor.position(), 2);
+
+    cursor.seek(SeekFrom::Start(u64::MAX - 1)).unwrap();
+    assert_eq!(
+        cursor.seek(SeekFrom::Current(i64::MAX)),
+        Err(Error::InvalidInput)
+    );
+    assert_eq!(cursor.position(), u64::MAX - 1);
+}
+
+#[test]
+fn stream_len_restores_position() {
+    let mut stream = SeekOnly {
+        inner: Cursor::new(&b"hello world"[..]),
+        seeks: 0,
+    };
+    stream.seek(SeekFrom::Start(3)).unwrap();
+    stream.seeks = 0;
+    assert_eq!(stream.stream_len().unwrap(), 11);
+    assert_eq!(stream.seeks, 3);
+    assert_eq!(stream.stream_position().unwrap(), 3);
+
+    // At the end, seeking back is skipped.
+    stream.seek(SeekFrom::End(0)).unwrap();
+    stream.seeks = 0;
+    assert_eq!(stream.stream_len().unwrap(), 11);
+    assert_eq!(stream.seeks, 2);
+    assert_eq!(stream.stream_position().unwrap(), 11);
+}
+
+#[test]
+fn seek_relative() {
+    let mut stream = SeekOnly {
+        inner: Cursor::new(&b"hello world"[..]),
+        seeks: 0,
+    };
+    stream.seek_relative(4).unwrap();
+    stream.seek_relative(-2).unwrap();
+    assert_eq!(stream.stream_position().unwrap(), 2);
+    assert_eq!(stream.seek_relative(-3), Err(Error::InvalidInput));
+    assert_eq!(stream.stream_position().unwrap(), 2);
+}
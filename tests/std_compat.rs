@@ -0,0 +1,77 @@
+#![cfg(feature = "std")]
+
+use std::io::{self, ErrorKind};
+
+use axio::{
+    from_std_error, from_std_error_kind, to_std_error, to_std_error_kind, Error, FromStd, Read,
+    ToStd,
+};
+
+mod common;
+
+use common::Mock;
+
+#[test]
+fn error_round_trip() {
+    for e in [
+        Error::WouldBlock,
+        Error::UnexpectedEof,
+        Error::BrokenPipe,
+        Error::BadState,
+        Error::Io,
+        Error::NoMemory,
+    ] {
+        assert_eq!(from_std_error(&to_std_error(e)), e);
+    }
+}
+
+#[test]
+fn error_kinds() {
+    for (e, kind) in [
+        (Error::WouldBlock, ErrorKind::WouldBlock),
+        (Error::UnexpectedEof, ErrorKind::UnexpectedEof),
+        (Error::BrokenPipe, ErrorKind::BrokenPipe),
+    ] {
+        assert_eq!(to_std_error_kind(e), kind);
+        assert_eq!(from_std_error_kind(kind), e);
+        assert_eq!(to_std_error(e).kind(), kind);
+    }
+
+    // Errors and kinds with no counterpart.
+    assert_eq!(to_std_error_kind(Error::BadState), ErrorKind::Other);
+    assert_eq!(from_std_error_kind(ErrorKind::Other), Error::Io);
+    assert_eq!(from_std_error_kind(ErrorKind::NetworkDown), Error::Io);
+}
+
+#[test]
+fn plain_std_error_maps_by_kind() {
+    let err = io::Error::new(ErrorKind::BrokenPipe, "gone");
+    assert_eq!(from_std_error(&err), Error::BrokenPipe);
+    let err = io::Error::other("custom");
+    assert_eq!(from_std_error(&err), Error::Io);
+}
+
+#[test]
+fn adapters_convert_errors() {
+    let mut reader = FromStd::new(io::empty());
+    let mut buf = [0; 1];
+    assert_eq!(reader.read_exact(&mut buf).unwrap_err(), Error::Io);
+
+    let mut reader = FromStd::new(io::Cursor::new(b"ab"));
+    let mut buf = [0; 3];
+    assert_eq!(reader.read_exact(&mut buf).unwrap_err(), Error::Io);
+}
+
+#[test]
+fn to_std_read_exact_eof() {
+    let mut reader = ToStd::new(&b"ab"[..]);
+    let mut buf = [0; 3];
+    let err = io::Read::read_exact(&mut reader, &mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+    // Short reads are retried.
+    let mut reader = ToStd::new(Mock::new(b"abcdef", 2));
+    let mut buf = [0; 6];
+    io::Read::read_exact(&mut reader, &mut buf).unwrap();
+    assert_eq!(&buf, b"abcdef");
+}